@@ -5,17 +5,32 @@ use near_plugins::{
 };
 use near_sdk::borsh::BorshDeserialize;
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::{env, ext_contract, near, Gas, PanicOnDefault, Promise, PromiseOrValue, PublicKey};
-use rlp::Rlp;
+use near_sdk::{
+    env, ext_contract, near, require, Gas, NearToken, PanicOnDefault, Promise, PromiseError,
+    PromiseOrValue, PromiseResult, PublicKey,
+};
+use rlp::{DecoderError, Rlp};
 
 type AccountId = String;
 
 /// Gas to call block_hash_safe
 const BLOCK_HASH_SAFE_GAS: Gas = Gas::from_tgas(10);
 
+/// Gas to call historical_block_hash_safe
+const HISTORICAL_BLOCK_HASH_SAFE_GAS: Gas = Gas::from_tgas(30);
+
 /// Gas to call on_block_hash
 const ON_BLOCK_HASH_GAS: Gas = Gas::from_tgas(5);
 
+/// Gas to call on_transaction_block_hash
+const ON_TRANSACTION_BLOCK_HASH_GAS: Gas = Gas::from_tgas(10);
+
+/// Gas to call on_log_entry_block_hash
+const ON_LOG_ENTRY_BLOCK_HASH_GAS: Gas = Gas::from_tgas(10);
+
+/// Gas to call on_block_hashes
+const ON_BLOCK_HASHES_GAS: Gas = Gas::from_tgas(10);
+
 #[derive(AccessControlRole, Deserialize, Serialize, Copy, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Role {
@@ -25,6 +40,9 @@ pub enum Role {
     UpgradableCodeStager,
     UpgradableCodeDeployer,
     DAO,
+    UnrestrictedVerifyTransaction,
+    UnrestrictedVerifyWithdrawal,
+    UnrestrictedVerifyAccount,
 }
 
 #[near(contract_state)]
@@ -44,12 +62,120 @@ pub struct EthProver {
     paused: u128,
 }
 
+/// Proof of the log entry included into the receipt of the block
+#[near(serializers=[borsh])]
+pub struct LogProof {
+    pub log_index: u64,
+    pub log_entry_data: Vec<u8>,
+    pub receipt_index: u64,
+    pub receipt_data: Vec<u8>,
+    pub header_data: Vec<u8>,
+    pub proof: Vec<Vec<u8>>,
+}
+
+/// Reason why the Merkle Patricia trie proof is not valid.
+/// The `depth` is the index of the failed node in the proof.
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofError {
+    /// The node doesn't match the root or the hash referenced by the parent node
+    HashMismatch { depth: u64 },
+    /// The node has `items` items instead of 17 for the branch or 2 for the leaf and extension
+    BadNodeArity { depth: u64, items: u64 },
+    /// The path of the leaf or extension node diverges from the key
+    KeyMismatch { depth: u64 },
+    /// The proof ends before the key is resolved
+    TruncatedProof { depth: u64 },
+    /// The proof has more nodes after the value is resolved
+    ExtraNodes { depth: u64 },
+    /// The node is not a valid RLP encoded trie node
+    InvalidNode { depth: u64 },
+}
+
+impl std::fmt::Display for ProofError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HashMismatch { depth } => write!(f, "hash mismatch at depth {}", depth),
+            Self::BadNodeArity { depth, items } => {
+                write!(f, "bad arity of {} items at depth {}", items, depth)
+            }
+            Self::KeyMismatch { depth } => write!(f, "key mismatch at depth {}", depth),
+            Self::TruncatedProof { depth } => write!(f, "truncated proof at depth {}", depth),
+            Self::ExtraNodes { depth } => write!(f, "extra nodes after depth {}", depth),
+            Self::InvalidNode { depth } => write!(f, "invalid node at depth {}", depth),
+        }
+    }
+}
+
+/// NEP-297 events emitted on the failed verifications, so the typed reason can be read from
+/// the receipt outcome of the call
+#[near(event_json(standard = "eth-prover"))]
+pub enum EthProverEvent {
+    /// The Merkle Patricia trie proof of the `proof_name` item, e.g. `receipt`, is not valid
+    #[event_version("1.0.0")]
+    InvalidProof {
+        proof_name: String,
+        error: ProofError,
+    },
+}
+
+/// Proof of the storage slot of the account
+#[near(serializers=[borsh])]
+pub struct StorageSlotProof {
+    /// Keccak-256 of the storage key
+    pub key_hash: Vec<u8>,
+    /// Expected storage value, the empty vec for the proof of non existence
+    pub value: Vec<u8>,
+    pub proof: Vec<Vec<u8>>,
+}
+
 /// Defines an interface to call EthProver back as a callback with the result from the
 /// EthClient contract.
 #[ext_contract(remote_self)]
 pub trait RemoteSelf {
     #[result_serializer(borsh)]
     fn on_block_hash(&self, #[serializer(borsh)] expected_block_hash: H256) -> bool;
+
+    #[result_serializer(borsh)]
+    fn on_block_hashes(
+        &self,
+        #[serializer(borsh)] expected_block_hashes: Vec<H256>,
+        #[serializer(borsh)] log_blocks: Vec<Option<u32>>,
+    ) -> Vec<bool>;
+
+    #[result_serializer(borsh)]
+    fn on_log_entry_block_hash(
+        &self,
+        #[serializer(borsh)] verified_log: VerifiedLog,
+    ) -> Option<VerifiedLog>;
+
+    #[result_serializer(borsh)]
+    fn on_transaction_block_hash(
+        &self,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] transaction: Transaction,
+    ) -> Option<Transaction>;
+
+    #[result_serializer(borsh)]
+    fn on_withdrawal_block_hash(
+        &self,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] withdrawal: Withdrawal,
+    ) -> Option<Withdrawal>;
+
+    #[result_serializer(borsh)]
+    fn on_account_block_hash(
+        &self,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] account: Account,
+    ) -> Option<Account>;
+
+    #[result_serializer(borsh)]
+    fn on_storage_slots_block_hash(
+        &self,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] results: Vec<bool>,
+    ) -> Vec<bool>;
 }
 
 /// Defines an interface to call EthClient contract to get the safe block hash for a given block
@@ -63,8 +189,8 @@ pub trait RemoteEthClient {
 
 /// Get element at position `pos` from rlp encoded data,
 /// and decode it as vector of bytes
-fn get_vec(data: &Rlp, pos: usize) -> Vec<u8> {
-    data.at(pos).unwrap().as_val::<Vec<u8>>().unwrap()
+fn get_vec(data: &Rlp, pos: usize) -> Result<Vec<u8>, DecoderError> {
+    data.at(pos)?.as_val::<Vec<u8>>()
 }
 
 #[near]
@@ -98,6 +224,112 @@ impl EthProver {
         return block_hash == Some(expected_block_hash);
     }
 
+    /// Implementation of the callback when the EthClient returns data for `verify_log_entries`.
+    /// The block hashes are read from the results of the EthClient calls, one per block, so the
+    /// failed call only fails the log entries of its block.
+    /// - `expected_block_hashes` are the block hashes that we expect in the same order
+    /// - `log_blocks` are the positions of the blocks of the log entries, `None` if the log entry
+    ///   failed the verification before the EthClient call
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_block_hashes(
+        &self,
+        #[serializer(borsh)] expected_block_hashes: Vec<H256>,
+        #[serializer(borsh)] log_blocks: Vec<Option<u32>>,
+    ) -> Vec<bool> {
+        let block_hashes: Vec<Result<Option<H256>, PromiseError>> = (0
+            ..env::promise_results_count())
+            .map(|index| match env::promise_result(index) {
+                PromiseResult::Successful(data) => {
+                    Option::<H256>::try_from_slice(&data).map_err(|_| PromiseError::Failed)
+                }
+                _ => Err(PromiseError::Failed),
+            })
+            .collect();
+
+        Self::check_block_hashes(block_hashes, expected_block_hashes, log_blocks)
+    }
+
+    /// Implementation of the callback when the EthClient returns data for
+    /// `verify_and_decode_log_entry`. Returns the verified log if the block hash is part of the
+    /// safe canonical chain.
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_log_entry_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[serializer(borsh)] verified_log: VerifiedLog,
+    ) -> Option<VerifiedLog> {
+        (block_hash == Some(verified_log.block_hash)).then_some(verified_log)
+    }
+
+    /// Implementation of the callback when the EthClient returns data for `verify_transaction`.
+    /// Returns the verified transaction if the block hash is part of the safe canonical chain.
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_transaction_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] transaction: Transaction,
+    ) -> Option<Transaction> {
+        (block_hash == Some(expected_block_hash)).then_some(transaction)
+    }
+
+    /// Implementation of the callback when the EthClient returns data for `verify_withdrawal`.
+    /// Returns the verified withdrawal if the block hash is part of the safe canonical chain.
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_withdrawal_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] withdrawal: Withdrawal,
+    ) -> Option<Withdrawal> {
+        (block_hash == Some(expected_block_hash)).then_some(withdrawal)
+    }
+
+    /// Implementation of the callback when the EthClient returns data for `verify_account`.
+    /// Returns the verified account if the block hash is part of the safe canonical chain.
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_account_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] account: Account,
+    ) -> Option<Account> {
+        (block_hash == Some(expected_block_hash)).then_some(account)
+    }
+
+    /// Implementation of the callback when the EthClient returns data for
+    /// `verify_storage_slots`. Returns the results of the storage slots if the block hash is
+    /// part of the safe canonical chain, otherwise all of them are `false`.
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_storage_slots_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] results: Vec<bool>,
+    ) -> Vec<bool> {
+        if block_hash == Some(expected_block_hash) {
+            results
+        } else {
+            vec![false; results.len()]
+        }
+    }
+
     /// Externally visible method to verify that the given block hash is part of the safe canonical
     /// chain on the remote EthClient contract.
     /// Returns a promise.
@@ -184,29 +416,296 @@ impl EthProver {
         max_header_height: Option<u64>,
         skip_bridge_call: bool,
     ) -> PromiseOrValue<bool> {
-        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        let log_proof = LogProof {
+            log_index,
+            log_entry_data,
+            receipt_index,
+            receipt_data,
+            header_data,
+            proof,
+        };
+        let verified_log =
+            match Self::verify_log_proof(log_proof, min_header_height, max_header_height) {
+                Some(verified_log) => verified_log,
+                None => return PromiseOrValue::Value(false),
+            };
+        if skip_bridge_call {
+            return PromiseOrValue::Value(true);
+        }
+
+        // Verify block header was in the bridge
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS)
+            .block_hash_safe(verified_log.block_number)
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_BLOCK_HASH_GAS)
+                    .on_block_hash(verified_log.block_hash),
+            )
+            .into()
+    }
+
+    /// Verifies the log entry like `verify_log_entry_in_bound`, but returns the decoded log entry
+    /// with its position in the chain, so the caller doesn't need to decode `log_entry_data`.
+    /// Returns `None` if the proof is not valid.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_and_decode_log_entry(
+        &self,
+        #[serializer(borsh)] log_index: u64,
+        #[serializer(borsh)] log_entry_data: Vec<u8>,
+        #[serializer(borsh)] receipt_index: u64,
+        #[serializer(borsh)] receipt_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Option<VerifiedLog>> {
+        let log_proof = LogProof {
+            log_index,
+            log_entry_data,
+            receipt_index,
+            receipt_data,
+            header_data,
+            proof,
+        };
+        let verified_log =
+            match Self::verify_log_proof(log_proof, min_header_height, max_header_height) {
+                Some(verified_log) => verified_log,
+                None => return PromiseOrValue::Value(None),
+            };
+        if skip_bridge_call {
+            return PromiseOrValue::Value(Some(verified_log));
+        }
+
+        // Verify block header was in the bridge
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS)
+            .block_hash_safe(verified_log.block_number)
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_LOG_ENTRY_BLOCK_HASH_GAS)
+                    .on_log_entry_block_hash(verified_log),
+            )
+            .into()
+    }
+
+    /// Verifies the batch of log entries. The log entries are grouped by block, so each block hash
+    /// is requested from the EthClient once and all of them are checked in a single callback.
+    /// Returns the verification result for each of the log proofs in the same order.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_log_entries(
+        &self,
+        #[serializer(borsh)] log_proofs: Vec<LogProof>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Vec<bool>> {
+        let mut blocks: Vec<(u64, H256)> = vec![];
+        let log_blocks: Vec<Option<u32>> = log_proofs
+            .into_iter()
+            .map(|log_proof| {
+                let verified_log =
+                    Self::verify_log_proof(log_proof, min_header_height, max_header_height)?;
+                let block = (verified_log.block_number, verified_log.block_hash);
+                let position = blocks.iter().position(|b| *b == block).unwrap_or_else(|| {
+                    blocks.push(block);
+                    blocks.len() - 1
+                });
+                Some(position as u32)
+            })
+            .collect();
+
+        if skip_bridge_call || blocks.is_empty() {
+            return PromiseOrValue::Value(log_blocks.iter().map(Option::is_some).collect());
+        }
+
+        // Each block is checked by a separate EthClient call, so the gas is checked up front
+        // instead of failing in the middle of the promises
+        let required_gas = BLOCK_HASH_SAFE_GAS
+            .saturating_mul(blocks.len() as u64)
+            .saturating_add(ON_BLOCK_HASHES_GAS);
+        let available_gas = env::prepaid_gas().saturating_sub(env::used_gas());
+        require!(
+            available_gas >= required_gas,
+            format!(
+                "Not enough gas to verify the log entries of {} blocks, required {} but got {}",
+                blocks.len(),
+                required_gas,
+                available_gas
+            )
+        );
+
+        // Verify block headers were in the bridge
+        let (block_numbers, expected_block_hashes): (Vec<u64>, Vec<H256>) =
+            blocks.into_iter().unzip();
+        block_numbers
+            .into_iter()
+            .map(|block_number| {
+                eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+                    .with_static_gas(BLOCK_HASH_SAFE_GAS)
+                    .block_hash_safe(block_number)
+            })
+            .reduce(Promise::and)
+            .unwrap()
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_BLOCK_HASHES_GAS)
+                    .on_block_hashes(expected_block_hashes, log_blocks),
+            )
+            .into()
+    }
+
+    /// Verifies the log entry of the block which is already garbage collected by the EthClient.
+    /// The block hash is proven by the `historical_proof`, i.e. the borsh serialized
+    /// `HistoricalBlockProof` of the EthClient, against the state root of the finalized beacon
+    /// block stored by the EthClient. Returns the decoded log entry or `None` if the proof is
+    /// not valid.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_historical_log_entry(
+        &self,
+        #[serializer(borsh)] log_proof: LogProof,
+        #[serializer(borsh)] historical_proof: Vec<u8>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+    ) -> PromiseOrValue<Option<VerifiedLog>> {
+        let verified_log =
+            match Self::verify_log_proof(log_proof, min_header_height, max_header_height) {
+                Some(verified_log) => verified_log,
+                None => return PromiseOrValue::Value(None),
+            };
+
+        // Verify block header by the historical proof in the bridge, the arguments are
+        // the borsh serialized block number followed by the proof
+        let args = [
+            near_sdk::borsh::to_vec(&verified_log.block_number).unwrap(),
+            historical_proof,
+        ]
+        .concat();
+        Promise::new(self.bridge_smart_contract.parse().unwrap())
+            .function_call(
+                "historical_block_hash_safe".to_owned(),
+                args,
+                NearToken::from_yoctonear(0),
+                HISTORICAL_BLOCK_HASH_SAFE_GAS,
+            )
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_LOG_ENTRY_BLOCK_HASH_GAS)
+                    .on_log_entry_block_hash(verified_log),
+            )
+            .into()
+    }
+
+    /// Verifies that the transaction is included into the block by the `transactions_root` of the
+    /// header. Supports the legacy and the EIP-2718 typed transactions, including the blob and the
+    /// EIP-7702 ones. Returns the decoded transaction or `None` if the proof is not valid.
+    #[pause(except(roles(Role::UnrestrictedVerifyTransaction, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_transaction(
+        &self,
+        #[serializer(borsh)] tx_index: u64,
+        #[serializer(borsh)] tx_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Option<Transaction>> {
+        let header: BlockHeader = match Self::decode_or_log("header", &header_data) {
+            Some(header) => header,
+            None => return PromiseOrValue::Value(None),
+        };
         if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
-            return PromiseOrValue::Value(false);
+            return PromiseOrValue::Value(None);
         }
 
-        let log_entry: LogEntry = rlp::decode(log_entry_data.as_slice()).unwrap();
-        let receipt: Receipt = rlp::decode(receipt_data.as_slice()).unwrap();
+        let transaction: Transaction = match Self::decode_or_log("transaction", &tx_data) {
+            Some(transaction) => transaction,
+            None => return PromiseOrValue::Value(None),
+        };
 
-        // Verify log_entry included in receipt
-        let log_index_usize = usize::try_from(log_index).expect("Invalid log_index");
-        assert_eq!(receipt.logs[log_index_usize], log_entry);
+        // Verify transaction included into header
+        let data = match Self::verify_trie_proof_or_log(
+            "transaction",
+            header.transactions_root,
+            rlp::encode(&tx_index).to_vec(),
+            proof,
+        ) {
+            Some(data) => data,
+            None => return PromiseOrValue::Value(None),
+        };
+        if data != tx_data {
+            return PromiseOrValue::Value(None);
+        } else if skip_bridge_call {
+            return PromiseOrValue::Value(Some(transaction));
+        }
 
-        // Verify receipt included into header
-        let data = Self::verify_trie_proof(
-            header.receipts_root,
-            rlp::encode(&receipt_index).to_vec(),
+        // Verify block header was in the bridge
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS)
+            .block_hash_safe(header.number)
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_TRANSACTION_BLOCK_HASH_GAS)
+                    .on_transaction_block_hash(header.hash.unwrap(), transaction),
+            )
+            .into()
+    }
+
+    /// Verifies that the withdrawal is included into the post-Shapella block by the
+    /// `withdrawals_root` of the header. The `withdrawal_position` is the position of the
+    /// withdrawal in the block, unlike the global index of the withdrawal.
+    /// Returns the decoded withdrawal or `None` if the proof is not valid.
+    #[pause(except(roles(Role::UnrestrictedVerifyWithdrawal, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_withdrawal(
+        &self,
+        #[serializer(borsh)] withdrawal_position: u64,
+        #[serializer(borsh)] withdrawal_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Option<Withdrawal>> {
+        let header: BlockHeader = match Self::decode_or_log("header", &header_data) {
+            Some(header) => header,
+            None => return PromiseOrValue::Value(None),
+        };
+        if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
+            return PromiseOrValue::Value(None);
+        }
+
+        let withdrawals_root = match header.withdrawals_root {
+            Some(withdrawals_root) => withdrawals_root,
+            None => {
+                env::log_str("The header has no withdrawals root");
+                return PromiseOrValue::Value(None);
+            }
+        };
+        let withdrawal: Withdrawal = match Self::decode_or_log("withdrawal", &withdrawal_data) {
+            Some(withdrawal) => withdrawal,
+            None => return PromiseOrValue::Value(None),
+        };
+
+        // Verify withdrawal included into header
+        let data = match Self::verify_trie_proof_or_log(
+            "withdrawal",
+            withdrawals_root,
+            rlp::encode(&withdrawal_position).to_vec(),
             proof,
-        );
-        let verification_result = receipt_data == data;
-        if verification_result && skip_bridge_call {
-            return PromiseOrValue::Value(true);
-        } else if !verification_result {
-            return PromiseOrValue::Value(false);
+        ) {
+            Some(data) => data,
+            None => return PromiseOrValue::Value(None),
+        };
+        if data != withdrawal_data {
+            return PromiseOrValue::Value(None);
+        } else if skip_bridge_call {
+            return PromiseOrValue::Value(Some(withdrawal));
         }
 
         // Verify block header was in the bridge
@@ -216,7 +715,63 @@ impl EthProver {
             .then(
                 remote_self::ext(env::current_account_id())
                     .with_static_gas(ON_BLOCK_HASH_GAS)
-                    .on_block_hash(header.hash.unwrap()),
+                    .on_withdrawal_block_hash(header.hash.unwrap(), withdrawal),
+            )
+            .into()
+    }
+
+    /// Verifies the Merkle Patricia trie proof of the `key` against the `expected_root`, e.g.
+    /// the `receipts_root` of the header with the `rlp(receipt_index)` key.
+    /// Returns the reason why the proof is not valid, or `None` if it is valid,
+    /// so the failed verification can be diagnosed without reproducing it.
+    #[result_serializer(borsh)]
+    pub fn check_trie_proof(
+        &self,
+        #[serializer(borsh)] expected_root: H256,
+        #[serializer(borsh)] key: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+    ) -> Option<ProofError> {
+        Self::verify_trie_proof(expected_root, key, proof).err()
+    }
+
+    /// Verifies the account by the `state_root` of the header and returns its nonce, balance,
+    /// storage root and code hash. The account that is not in the state trie is returned as
+    /// `Account::empty()`. Returns `None` if the proof is not valid.
+    #[pause(except(roles(Role::UnrestrictedVerifyAccount, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_account(
+        &self,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] address: Address,
+        #[serializer(borsh)] account_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Option<Account>> {
+        let header: BlockHeader = match Self::decode_or_log("header", &header_data) {
+            Some(header) => header,
+            None => return PromiseOrValue::Value(None),
+        };
+        if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
+            return PromiseOrValue::Value(None);
+        }
+
+        let account = match Self::verify_account_proof(header.state_root, &address, account_proof) {
+            Some(account) => account,
+            None => return PromiseOrValue::Value(None),
+        };
+        if skip_bridge_call {
+            return PromiseOrValue::Value(Some(account));
+        }
+
+        // Verify block header was in the bridge
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS)
+            .block_hash_safe(header.number)
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_BLOCK_HASH_GAS)
+                    .on_account_block_hash(header.hash.unwrap(), account),
             )
             .into()
     }
@@ -240,20 +795,45 @@ impl EthProver {
         #[serializer(borsh)] max_header_height: Option<u64>,
         #[serializer(borsh)] skip_bridge_call: bool,
     ) -> PromiseOrValue<bool> {
-        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        let header: BlockHeader = match Self::decode_or_log("header", &header_data) {
+            Some(header) => header,
+            None => return PromiseOrValue::Value(false),
+        };
         if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
             return PromiseOrValue::Value(false);
         }
 
         let account_key = near_keccak256(&contract_address).to_vec();
-        let account_state = Self::verify_trie_proof(header.state_root, account_key, account_proof);
+        let account_state = match Self::verify_trie_proof_or_log(
+            "account",
+            header.state_root,
+            account_key,
+            account_proof,
+        ) {
+            Some(account_state) => account_state,
+            None => return PromiseOrValue::Value(false),
+        };
         if account_state != expected_account_state {
             env::log_str("account_state != expected_account_state");
             return PromiseOrValue::Value(false);
         }
 
-        let storage_hash: H256 = Rlp::new(&account_state).val_at(2).unwrap();
-        let storage_value = Self::verify_trie_proof(storage_hash, storage_key_hash, storage_proof);
+        let storage_hash: H256 = match Rlp::new(&account_state).val_at(2) {
+            Ok(storage_hash) => storage_hash,
+            Err(error) => {
+                env::log_str(&format!("Invalid account state: {}", error));
+                return PromiseOrValue::Value(false);
+            }
+        };
+        let storage_value = match Self::verify_trie_proof_or_log(
+            "storage",
+            storage_hash,
+            storage_key_hash,
+            storage_proof,
+        ) {
+            Some(storage_value) => storage_value,
+            None => return PromiseOrValue::Value(false),
+        };
         if storage_value != expected_storage_value {
             env::log_str("storage_value != expected_storage_value");
             return PromiseOrValue::Value(false);
@@ -275,6 +855,169 @@ impl EthProver {
             .into()
     }
 
+    /// Verifies the storage slots of the account with the single account proof and the single
+    /// EthClient call. See `verify_storage_proof` for the values of the non existent slots.
+    /// Returns the verification result for each of the slots in the same order.
+    #[pause(except(roles(Role::UnrestrictedVerifyStorageProof, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_storage_slots(
+        &self,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] address: Address,
+        #[serializer(borsh)] account_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] storage_slots: Vec<StorageSlotProof>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Vec<bool>> {
+        let header: BlockHeader = match Self::decode_or_log("header", &header_data) {
+            Some(header) => header,
+            None => return PromiseOrValue::Value(vec![false; storage_slots.len()]),
+        };
+        if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
+            return PromiseOrValue::Value(vec![false; storage_slots.len()]);
+        }
+
+        let account = match Self::verify_account_proof(header.state_root, &address, account_proof) {
+            Some(account) => account,
+            None => return PromiseOrValue::Value(vec![false; storage_slots.len()]),
+        };
+
+        let results: Vec<bool> = storage_slots
+            .into_iter()
+            .map(|slot| {
+                Self::verify_trie_proof_or_log(
+                    "storage",
+                    account.storage_root,
+                    slot.key_hash,
+                    slot.proof,
+                ) == Some(slot.value)
+            })
+            .collect();
+        if skip_bridge_call || !results.contains(&true) {
+            return PromiseOrValue::Value(results);
+        }
+
+        // Verify block header was in the bridge
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS)
+            .block_hash_safe(header.number)
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_BLOCK_HASH_GAS)
+                    .on_storage_slots_block_hash(header.hash.unwrap(), results),
+            )
+            .into()
+    }
+
+    /// Verifies the account proof against the state root and decodes the account state.
+    fn verify_account_proof(
+        state_root: H256,
+        address: &Address,
+        account_proof: Vec<Vec<u8>>,
+    ) -> Option<Account> {
+        let account_key = near_keccak256(address.0.as_bytes()).to_vec();
+        let account_state =
+            Self::verify_trie_proof_or_log("account", state_root, account_key, account_proof)?;
+        if account_state.is_empty() {
+            Some(Account::empty())
+        } else {
+            Self::decode_or_log("account state", &account_state)
+        }
+    }
+
+    /// Verifies the log entry against the receipts root of the header without the EthClient call.
+    /// Returns the decoded log entry if it is included into the block within the bounds.
+    fn verify_log_proof(
+        log_proof: LogProof,
+        min_header_height: Option<u64>,
+        max_header_height: Option<u64>,
+    ) -> Option<VerifiedLog> {
+        let header: BlockHeader = Self::decode_or_log("header", &log_proof.header_data)?;
+        if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
+            return None;
+        }
+
+        let log_entry: LogEntry = Self::decode_or_log("log entry", &log_proof.log_entry_data)?;
+        let receipt: Receipt = Self::decode_or_log("receipt", &log_proof.receipt_data)?;
+
+        // Verify log_entry included in receipt
+        if !Self::is_log_entry_in_receipt(&log_entry, &receipt, log_proof.log_index) {
+            return None;
+        }
+
+        // Verify receipt included into header
+        let data = Self::verify_trie_proof_or_log(
+            "receipt",
+            header.receipts_root,
+            rlp::encode(&log_proof.receipt_index).to_vec(),
+            log_proof.proof,
+        )?;
+        (data == log_proof.receipt_data).then(|| VerifiedLog {
+            address: log_entry.address,
+            topics: log_entry.topics,
+            data: log_entry.data,
+            block_number: header.number,
+            block_hash: header.hash.unwrap(),
+            receipt_index: log_proof.receipt_index,
+            log_index: log_proof.log_index,
+        })
+    }
+
+    /// Returns the result of each log entry by the result of the EthClient call for its block.
+    /// The log entry fails if the call failed or returned the unexpected block hash.
+    fn check_block_hashes(
+        block_hashes: Vec<Result<Option<H256>, PromiseError>>,
+        expected_block_hashes: Vec<H256>,
+        log_blocks: Vec<Option<u32>>,
+    ) -> Vec<bool> {
+        log_blocks
+            .into_iter()
+            .map(|block| {
+                block.is_some_and(|position| {
+                    let position = position as usize;
+                    matches!(
+                        (block_hashes.get(position), expected_block_hashes.get(position)),
+                        (Some(Ok(Some(block_hash))), Some(expected_block_hash))
+                            if block_hash == expected_block_hash
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Checks that the log entry is at `log_index` of the receipt, logs the reason if it's not
+    fn is_log_entry_in_receipt(log_entry: &LogEntry, receipt: &Receipt, log_index: u64) -> bool {
+        let receipt_log = usize::try_from(log_index)
+            .ok()
+            .and_then(|log_index| receipt.logs.get(log_index));
+        match receipt_log {
+            Some(receipt_log) if receipt_log == log_entry => true,
+            Some(_) => {
+                env::log_str(&format!(
+                    "The log entry doesn't match the log {} of the receipt",
+                    log_index
+                ));
+                false
+            }
+            None => {
+                env::log_str(&format!(
+                    "The log index {} is out of the {} logs of the receipt",
+                    log_index,
+                    receipt.logs.len()
+                ));
+                false
+            }
+        }
+    }
+
+    /// Decodes the RLP encoded `item_name`, e.g. `header`, and logs the reason if it's not valid
+    fn decode_or_log<T: rlp::Decodable>(item_name: &str, data: &[u8]) -> Option<T> {
+        rlp::decode(data)
+            .map_err(|error| env::log_str(&format!("Invalid {}: {}", item_name, error)))
+            .ok()
+    }
+
     fn is_block_height_in_bound(
         header_height: u64,
         min_header_height: Option<u64>,
@@ -309,6 +1052,44 @@ impl EthProver {
         true
     }
 
+    /// Verifies the trie proof, logging a typed error and emitting it as `InvalidProof` before mapping it to `None`.
+    fn verify_trie_proof_or_log(
+        proof_name: &str,
+        expected_root: H256,
+        key: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        Self::verify_trie_proof(expected_root, key, proof)
+            .map_err(|error| {
+                env::log_str(&format!("Invalid {} proof: {}", proof_name, error));
+                EthProverEvent::InvalidProof {
+                    proof_name: proof_name.to_owned(),
+                    error,
+                }
+                .emit();
+            })
+            .ok()
+    }
+
+    fn verify_trie_proof(
+        expected_root: H256,
+        key: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+    ) -> Result<Vec<u8>, ProofError> {
+        // The empty trie has no nodes, so the empty proof proves the absence of any key,
+        // e.g. `eth_getProof` returns the empty storage proof for the account without storage
+        if proof.is_empty() && expected_root == H256::from(EMPTY_TRIE_ROOT) {
+            return Ok(vec![]);
+        }
+
+        let mut actual_key = vec![];
+        for el in key {
+            actual_key.push(el / 16);
+            actual_key.push(el % 16);
+        }
+        Self::_verify_trie_proof((expected_root.0).0.into(), &actual_key, &proof, 0, 0)
+    }
+
     /// Verify the proof recursively traversing through the key.
     /// Return the value at the end of the key, in case the proof is valid.
     ///
@@ -322,44 +1103,45 @@ impl EthProver {
     /// Verification:  https://github.com/slockit/in3/wiki/Ethereum-Verification-and-MerkleProof#receipt-proof
     /// Article:       https://medium.com/@ouvrard.pierre.alain/merkle-proof-verification-for-ethereum-patricia-tree-48f29658eec
     /// Python impl:   https://gist.github.com/mfornet/0ff283274c0162f1cca45966bccf69ee
-    ///
-    fn verify_trie_proof(expected_root: H256, key: Vec<u8>, proof: Vec<Vec<u8>>) -> Vec<u8> {
-        let mut actual_key = vec![];
-        for el in key {
-            actual_key.push(el / 16);
-            actual_key.push(el % 16);
-        }
-        Self::_verify_trie_proof((expected_root.0).0.into(), &actual_key, &proof, 0, 0)
-    }
-
     fn _verify_trie_proof(
         expected_root: Vec<u8>,
         key: &Vec<u8>,
         proof: &Vec<Vec<u8>>,
         key_index: usize,
         proof_index: usize,
-    ) -> Vec<u8> {
-        let node = &proof[proof_index];
+    ) -> Result<Vec<u8>, ProofError> {
+        let depth = proof_index as u64;
+        let invalid_node = |_: DecoderError| ProofError::InvalidNode { depth };
+        let node = proof
+            .get(proof_index)
+            .ok_or(ProofError::TruncatedProof { depth })?;
 
-        if key_index == 0 {
+        let is_hash_valid = if key_index == 0 {
             // trie root is always a hash
-            assert_eq!(near_keccak256(node), expected_root.as_slice());
+            near_keccak256(node) == expected_root.as_slice()
         } else if node.len() < 32 {
             // if rlp < 32 bytes, then it is not hashed
-            assert_eq!(node.as_slice(), expected_root);
+            node.as_slice() == expected_root
         } else {
-            assert_eq!(near_keccak256(node), expected_root.as_slice());
+            near_keccak256(node) == expected_root.as_slice()
+        };
+        if !is_hash_valid {
+            return Err(ProofError::HashMismatch { depth });
         }
 
         let node = Rlp::new(&node.as_slice());
+        let items = node.item_count().map_err(invalid_node)?;
 
-        if node.iter().count() == 17 {
+        if items == 17 {
             // Branch node
             if key_index >= key.len() {
-                assert_eq!(proof_index + 1, proof.len());
-                get_vec(&node, 16)
+                if proof_index + 1 != proof.len() {
+                    return Err(ProofError::ExtraNodes { depth });
+                }
+                get_vec(&node, 16).map_err(invalid_node)
             } else {
-                let new_expected_root = get_vec(&node, key[key_index] as usize);
+                let new_expected_root =
+                    get_vec(&node, key[key_index] as usize).map_err(invalid_node)?;
                 if !new_expected_root.is_empty() {
                     Self::_verify_trie_proof(
                         new_expected_root,
@@ -370,17 +1152,17 @@ impl EthProver {
                     )
                 } else {
                     // not included in proof
-                    vec![]
+                    Ok(vec![])
                 }
             }
-        } else {
+        } else if items == 2 {
             // Leaf or extension node
-            assert_eq!(node.iter().count(), 2);
-            let path_u8 = get_vec(&node, 0);
+            let path_u8 = get_vec(&node, 0).map_err(invalid_node)?;
             // Extract first nibble
-            let head = path_u8[0] / 16;
-            // assert!(0 <= head); is implicit because of type limits
-            assert!(head <= 3);
+            let head = path_u8.first().ok_or(ProofError::InvalidNode { depth })? / 16;
+            if head > 3 {
+                return Err(ProofError::InvalidNode { depth });
+            }
 
             // Extract path
             let mut path = vec![];
@@ -391,20 +1173,31 @@ impl EthProver {
                 path.push(val / 16);
                 path.push(val % 16);
             }
+            let key_path = key.get(key_index..key_index + path.len());
 
             if head >= 2 {
                 // Leaf node
-                assert_eq!(proof_index + 1, proof.len());
-                assert_eq!(key_index + path.len(), key.len());
-                if path.as_slice() == &key[key_index..key_index + path.len()] {
-                    get_vec(&node, 1)
+                if proof_index + 1 != proof.len() {
+                    return Err(ProofError::ExtraNodes { depth });
+                }
+                if key_index + path.len() != key.len() {
+                    return Err(ProofError::KeyMismatch { depth });
+                }
+                if key_path == Some(path.as_slice()) {
+                    get_vec(&node, 1).map_err(invalid_node)
                 } else {
-                    vec![]
+                    Ok(vec![])
                 }
             } else {
                 // Extension node
-                assert_eq!(path.as_slice(), &key[key_index..key_index + path.len()]);
-                let new_expected_root = get_vec(&node, 1);
+                if key_path != Some(path.as_slice()) {
+                    // The key diverges from the path, so the proof of absence ends here
+                    if proof_index + 1 != proof.len() {
+                        return Err(ProofError::ExtraNodes { depth });
+                    }
+                    return Ok(vec![]);
+                }
+                let new_expected_root = get_vec(&node, 1).map_err(invalid_node)?;
                 Self::_verify_trie_proof(
                     new_expected_root,
                     key,
@@ -413,6 +1206,11 @@ impl EthProver {
                     proof_index + 1,
                 )
             }
+        } else {
+            Err(ProofError::BadNodeArity {
+                depth,
+                items: items as u64,
+            })
         }
     }
 
@@ -440,4 +1238,8 @@ impl EthProver {
 
 #[cfg(test)]
 mod tests;
+mod tests_log_entries;
 mod tests_storage_proof;
+mod tests_transaction_proof;
+mod tests_trie_proof;
+mod tests_withdrawal_proof;
@@ -476,7 +476,7 @@ mod tests {
 
         assert_eq!(
             EthProver::verify_trie_proof(expected_root, key, proof),
-            expected_value
+            Ok(expected_value)
         );
     }
 }
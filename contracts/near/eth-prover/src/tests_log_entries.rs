@@ -0,0 +1,286 @@
+#[cfg(test)]
+mod tests_log_entries {
+    use crate::{EthProver, LogProof, ProofError};
+    use eth_types::*;
+    use hex::FromHex;
+    use near_sdk::test_utils::get_logs;
+    use near_sdk::{PromiseOrValue, PromiseResult};
+    use rlp::RlpStream;
+
+    use near_sdk::{testing_env, NearToken, VMContext};
+
+    // The log entry of the transaction 0xb540248a9cca048c5861dec953d7a776bc1944319b9bd27a462469c8a437f4ff
+    const HEADER_DATA: &str = "f9021aa0f779e50b45bc27e4ed236840e5dbcf7afab50beaf553be56bf76da977e10cc73a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d493479452bc44d5378309ee2abf1539bf71de1b7d7be3b5a014c996b6934d7991643669e145b8355c63aa02cbde63d390fcf4e6181d5eea45a079b7e79dc739c31662fe6f25f65bf5a5d14299c7a7aa42c3f75b9fb05474f54ca0e28dc05418692cb7baab7e7f85c1dedb8791c275b797ea3b1ffcaec5ef2aa271b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000903234373439353837313930323034343383890fe68395ba8e82d0d9845dd84a079150505945206e616e6f706f6f6c2e6f7267a0a35425f443452cf94ba4b698b00fd7b3ff4fc671dea3d5cc2dcbedbc3766f45e88af7fec6031063a17";
+    const RECEIPT_DATA: &str = "f901a60182d0d9b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000f89df89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000006cc5f688a315f3dc28a7781717a9a798a59fda7ba00000000000000000000000007e7a32d9dc98c485c489be8e732f97b4ffe3a4cda000000000000000000000000000000000000000000000000000000001a13b8600";
+    const LOG_ENTRY_DATA: &str = "f89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000006cc5f688a315f3dc28a7781717a9a798a59fda7ba00000000000000000000000007e7a32d9dc98c485c489be8e732f97b4ffe3a4cda000000000000000000000000000000000000000000000000000000001a13b8600";
+
+    fn get_context(input: Vec<u8>) -> VMContext {
+        VMContext {
+            current_account_id: "alice.near".parse().unwrap(),
+            signer_account_id: "bob.near".parse().unwrap(),
+            signer_account_pk: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap(),
+            predecessor_account_id: "carol.near".parse().unwrap(),
+            input: input.into(),
+            block_index: 0,
+            block_timestamp: 0,
+            epoch_height: 0,
+            account_balance: NearToken::from_near(0),
+            account_locked_balance: NearToken::from_near(0),
+            storage_usage: 0,
+            attached_deposit: NearToken::from_near(0),
+            prepaid_gas: near_sdk::Gas::from_tgas(1_000_000),
+            random_seed: vec![1; 32].try_into().unwrap(),
+            view_config: None,
+            output_data_receivers: vec![],
+        }
+    }
+
+    fn log_proof(log_index: u64) -> LogProof {
+        let receipt_data = Vec::from_hex(RECEIPT_DATA).unwrap();
+        // The receipt is the only one in the block, so the trie is the single leaf of `rlp(0)`
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&vec![0x20u8, 0x80]);
+        stream.append(&receipt_data);
+
+        LogProof {
+            log_index,
+            log_entry_data: Vec::from_hex(LOG_ENTRY_DATA).unwrap(),
+            receipt_index: 0,
+            receipt_data,
+            header_data: Vec::from_hex(HEADER_DATA).unwrap(),
+            proof: vec![stream.out().to_vec()],
+        }
+    }
+
+    fn verify_log_entries(
+        log_proofs: Vec<LogProof>,
+        min_header_height: Option<u64>,
+        skip_bridge_call: bool,
+    ) -> PromiseOrValue<Vec<bool>> {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        contract.verify_log_entries(log_proofs, min_header_height, None, skip_bridge_call)
+    }
+
+    #[test]
+    pub fn test_verify_log_entries() {
+        let mut wrong_receipt = log_proof(0);
+        wrong_receipt.receipt_index = 1;
+        let log_proofs = vec![log_proof(0), log_proof(1), wrong_receipt, log_proof(0)];
+
+        match verify_log_entries(log_proofs, None, true) {
+            PromiseOrValue::Value(results) => assert_eq!(results, vec![true, false, false, true]),
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+    }
+
+    #[test]
+    pub fn test_verify_log_entries_out_of_bound_height() {
+        let header: BlockHeader = rlp::decode(&Vec::from_hex(HEADER_DATA).unwrap()).unwrap();
+
+        match verify_log_entries(vec![log_proof(0)], Some(header.number + 1), false) {
+            PromiseOrValue::Value(results) => assert_eq!(results, vec![false]),
+            PromiseOrValue::Promise(_) => panic!("No blocks to check in the bridge"),
+        }
+    }
+
+    #[test]
+    pub fn test_verify_log_entries_calls_bridge() {
+        match verify_log_entries(vec![log_proof(0), log_proof(0)], None, false) {
+            PromiseOrValue::Value(_) => panic!("The block hash should be checked in the bridge"),
+            PromiseOrValue::Promise(_) => {}
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough gas to verify the log entries of 1 blocks")]
+    pub fn test_verify_log_entries_with_not_enough_gas() {
+        let mut context = get_context(vec![]);
+        context.prepaid_gas = near_sdk::Gas::from_tgas(15);
+        testing_env!(context);
+        let contract = EthProver::init("ethbridge".to_string());
+
+        let _ = contract.verify_log_entries(vec![log_proof(0)], None, None, false);
+    }
+
+    #[test]
+    pub fn test_on_block_hashes() {
+        let first_hash = H256::from([0x11; 32]);
+        let second_hash = H256::from([0x22; 32]);
+        let third_hash = H256::from([0x33; 32]);
+
+        let mut context = get_context(vec![]);
+        context.predecessor_account_id = context.current_account_id.clone();
+        testing_env!(
+            context,
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![
+                PromiseResult::Successful(borsh::to_vec(&Some(first_hash)).unwrap()),
+                PromiseResult::Successful(borsh::to_vec(&Option::<H256>::None).unwrap()),
+                PromiseResult::Failed,
+            ]
+        );
+        let contract = EthProver::init("ethbridge".to_string());
+
+        // The failed call of the third block only fails its own log entry
+        let results = contract.on_block_hashes(
+            vec![first_hash, second_hash, third_hash],
+            vec![Some(0), None, Some(1), Some(2), Some(0)],
+        );
+        assert_eq!(results, vec![true, false, false, false, true]);
+    }
+
+    fn verify_and_decode_log_entry(log_proof: LogProof) -> Option<VerifiedLog> {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+
+        match contract.verify_and_decode_log_entry(
+            log_proof.log_index,
+            log_proof.log_entry_data,
+            log_proof.receipt_index,
+            log_proof.receipt_data,
+            log_proof.header_data,
+            log_proof.proof,
+            None,
+            None,
+            true,
+        ) {
+            PromiseOrValue::Value(verified_log) => verified_log,
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+    }
+
+    #[test]
+    pub fn test_verify_and_decode_log_entry() {
+        let header: BlockHeader = rlp::decode(&Vec::from_hex(HEADER_DATA).unwrap()).unwrap();
+        let log_entry: LogEntry = rlp::decode(&Vec::from_hex(LOG_ENTRY_DATA).unwrap()).unwrap();
+
+        let verified_log = verify_and_decode_log_entry(log_proof(0)).unwrap();
+        assert_eq!(verified_log.address, log_entry.address);
+        assert_eq!(verified_log.topics, log_entry.topics);
+        assert_eq!(verified_log.data, log_entry.data);
+        assert_eq!(verified_log.block_number, header.number);
+        assert_eq!(verified_log.block_hash, header.hash.unwrap());
+        assert_eq!(verified_log.receipt_index, 0);
+        assert_eq!(verified_log.log_index, 0);
+
+        let json = serde_json::to_string(&verified_log).unwrap();
+        assert_eq!(
+            serde_json::from_str::<VerifiedLog>(&json).unwrap(),
+            verified_log
+        );
+    }
+
+    #[test]
+    pub fn test_verify_and_decode_log_entry_with_wrong_log_index() {
+        assert_eq!(verify_and_decode_log_entry(log_proof(1)), None);
+    }
+
+    fn verify_log_entry(log_proof: LogProof) -> PromiseOrValue<bool> {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        contract.verify_log_entry(
+            log_proof.log_index,
+            log_proof.log_entry_data,
+            log_proof.receipt_index,
+            log_proof.receipt_data,
+            log_proof.header_data,
+            log_proof.proof,
+            true,
+        )
+    }
+
+    #[test]
+    pub fn test_verify_log_entry_with_invalid_data() {
+        match verify_log_entry(log_proof(1)) {
+            PromiseOrValue::Value(result) => assert!(!result),
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+        assert!(
+            get_logs().contains(&"The log index 1 is out of the 1 logs of the receipt".to_string())
+        );
+
+        let mut invalid_header = log_proof(0);
+        invalid_header.header_data = vec![0x01];
+        match verify_log_entry(invalid_header) {
+            PromiseOrValue::Value(result) => assert!(!result),
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.starts_with("Invalid header: ")));
+    }
+
+    #[test]
+    pub fn test_verify_log_entry_emits_invalid_proof_event() {
+        let mut invalid_proof = log_proof(0);
+        invalid_proof.proof = vec![vec![0x01]];
+        match verify_log_entry(invalid_proof) {
+            PromiseOrValue::Value(result) => assert!(!result),
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+
+        let logs = get_logs();
+        assert!(logs.contains(&"Invalid receipt proof: hash mismatch at depth 0".to_string()));
+        let event: serde_json::Value = serde_json::from_str(
+            logs.iter()
+                .find_map(|log| log.strip_prefix("EVENT_JSON:"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(event["standard"], "eth-prover");
+        assert_eq!(event["event"], "invalid_proof");
+        assert_eq!(event["data"]["proof_name"], "receipt");
+        assert_eq!(
+            serde_json::from_value::<ProofError>(event["data"]["error"].clone()).unwrap(),
+            ProofError::HashMismatch { depth: 0 }
+        );
+    }
+
+    #[test]
+    pub fn test_verify_historical_log_entry() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let historical_proof = vec![0x01, 0x02];
+
+        match contract.verify_historical_log_entry(
+            log_proof(0),
+            historical_proof.clone(),
+            None,
+            None,
+        ) {
+            PromiseOrValue::Value(_) => panic!("The block hash should be checked in the bridge"),
+            PromiseOrValue::Promise(_) => {}
+        }
+
+        match contract.verify_historical_log_entry(log_proof(1), historical_proof, None, None) {
+            PromiseOrValue::Value(verified_log) => assert_eq!(verified_log, None),
+            PromiseOrValue::Promise(_) => {
+                panic!("The invalid log proof is rejected before the call")
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_on_log_entry_block_hash() {
+        let verified_log = verify_and_decode_log_entry(log_proof(0)).unwrap();
+        let contract = EthProver::init("ethbridge".to_string());
+
+        assert_eq!(
+            contract.on_log_entry_block_hash(Some(verified_log.block_hash), verified_log.clone()),
+            Some(verified_log.clone())
+        );
+        assert_eq!(
+            contract.on_log_entry_block_hash(None, verified_log.clone()),
+            None
+        );
+        assert_eq!(
+            contract.on_log_entry_block_hash(Some(H256::from([0x11; 32])), verified_log),
+            None
+        );
+    }
+}
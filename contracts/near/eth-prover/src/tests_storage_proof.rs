@@ -1,10 +1,13 @@
 #[cfg(test)]
 mod tests_storage_proof {
-    use crate::EthProver;
-    use eth_types::H256;
+    use crate::{EthProver, StorageSlotProof};
+    use eth_types::{
+        near_keccak256, Account, Address, BlockHeader, EMPTY_CODE_HASH, EMPTY_TRIE_ROOT, H256, U256,
+    };
     use near_sdk::serde_json;
+    use near_sdk::test_utils::get_logs;
     use near_sdk::PromiseOrValue;
-    use rlp::Rlp;
+    use rlp::{Rlp, RlpStream};
     use serde::Deserialize;
 
     #[derive(Debug, Deserialize)]
@@ -118,7 +121,7 @@ mod tests_storage_proof {
                 test_data.storage_key_hash,
                 test_data.storage_proof
             ),
-            test_data.expected_storage_value
+            Ok(test_data.expected_storage_value)
         );
     }
 
@@ -145,14 +148,13 @@ mod tests_storage_proof {
     }
 
     #[test]
-    #[should_panic(expected = "assertion `left == right` failed")]
     pub fn test_verify_storage_proof_with_wrong_account_proof() {
         testing_env!(get_context(vec![]));
         let contract = EthProver::init("ethbridge".to_string());
         let test_data = get_storage_proof(String::from(
             "./src/test_data/storageProofWithIncorrectAccountProof.json",
         ));
-        if let PromiseOrValue::Value(true) = contract.verify_storage_proof(
+        if let PromiseOrValue::Value(false) = contract.verify_storage_proof(
             test_data.header_data,
             test_data.account_proof,
             test_data.contract_address,
@@ -167,17 +169,18 @@ mod tests_storage_proof {
         } else {
             panic!();
         }
+        let logs = get_logs();
+        assert!(logs.contains(&"Invalid account proof: hash mismatch at depth 0".to_string()));
     }
 
     #[test]
-    #[should_panic(expected = "assertion `left == right` failed")]
     pub fn test_verify_storage_proof_with_wrong_state_proof() {
         testing_env!(get_context(vec![]));
         let contract = EthProver::init("ethbridge".to_string());
         let test_data = get_storage_proof(String::from(
             "./src/test_data/storageProofWithIncorrectStateProof.json",
         ));
-        if let PromiseOrValue::Value(true) = contract.verify_storage_proof(
+        if let PromiseOrValue::Value(false) = contract.verify_storage_proof(
             test_data.header_data,
             test_data.account_proof,
             test_data.contract_address,
@@ -192,6 +195,8 @@ mod tests_storage_proof {
         } else {
             panic!();
         }
+        let logs = get_logs();
+        assert!(logs.contains(&"Invalid storage proof: hash mismatch at depth 0".to_string()));
     }
 
     #[test]
@@ -293,4 +298,207 @@ mod tests_storage_proof {
             panic!();
         }
     }
+
+    fn verify_account(
+        header_data: Vec<u8>,
+        contract_address: Vec<u8>,
+        account_proof: Vec<Vec<u8>>,
+    ) -> Option<Account> {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        match contract.verify_account(
+            header_data,
+            Address::from(contract_address),
+            account_proof,
+            None,
+            None,
+            true,
+        ) {
+            PromiseOrValue::Value(account) => account,
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+    }
+
+    #[test]
+    pub fn test_verify_account() {
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        let account = verify_account(
+            test_data.header_data,
+            test_data.contract_address,
+            test_data.account_proof,
+        )
+        .unwrap();
+
+        let expected_account_state = Rlp::new(&test_data.expected_account_state);
+        assert_eq!(account.nonce, 1);
+        assert_eq!(
+            account.balance,
+            expected_account_state.val_at::<U256>(1).unwrap()
+        );
+        assert_eq!(
+            account.storage_root,
+            expected_account_state.val_at::<H256>(2).unwrap()
+        );
+        assert_eq!(
+            account.code_hash,
+            expected_account_state.val_at::<H256>(3).unwrap()
+        );
+        assert!(account.is_contract());
+    }
+
+    #[test]
+    pub fn test_verify_account_with_wrong_account_proof() {
+        let test_data = get_storage_proof(String::from(
+            "./src/test_data/storageProofWithIncorrectAccountProof.json",
+        ));
+        let account = verify_account(
+            test_data.header_data,
+            test_data.contract_address,
+            test_data.account_proof,
+        );
+
+        assert_eq!(account, None);
+        let logs = get_logs();
+        assert!(logs.contains(&"Invalid account proof: hash mismatch at depth 0".to_string()));
+    }
+
+    #[test]
+    pub fn test_verify_absent_account() {
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        // The state trie with the single account of another address
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&[vec![0x20u8], vec![0x11u8; 32]].concat());
+        stream.append(&test_data.expected_account_state);
+        let leaf = stream.out().to_vec();
+        let mut header: BlockHeader = rlp::decode(&test_data.header_data).unwrap();
+        header.state_root = near_keccak256(&leaf).into();
+
+        let account = verify_account(
+            rlp::encode(&header).to_vec(),
+            test_data.contract_address,
+            vec![leaf],
+        )
+        .unwrap();
+
+        assert_eq!(account, Account::empty());
+        assert!(!account.is_contract());
+    }
+
+    fn verify_storage_slots(
+        test_data: StorageProof,
+        storage_slots: Vec<StorageSlotProof>,
+    ) -> Vec<bool> {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        match contract.verify_storage_slots(
+            test_data.header_data,
+            Address::from(test_data.contract_address),
+            test_data.account_proof,
+            storage_slots,
+            None,
+            None,
+            true,
+        ) {
+            PromiseOrValue::Value(results) => results,
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+    }
+
+    fn storage_slot(test_data: &StorageProof) -> StorageSlotProof {
+        StorageSlotProof {
+            key_hash: test_data.storage_key_hash.clone(),
+            value: test_data.expected_storage_value.clone(),
+            proof: test_data.storage_proof.clone(),
+        }
+    }
+
+    #[test]
+    pub fn test_verify_storage_slots() {
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        let mut wrong_value = storage_slot(&test_data);
+        wrong_value.value = vec![0x02];
+        let mut truncated_proof = storage_slot(&test_data);
+        truncated_proof.proof.pop();
+        let storage_slots = vec![
+            storage_slot(&test_data),
+            wrong_value,
+            truncated_proof,
+            storage_slot(&test_data),
+        ];
+
+        assert_eq!(
+            verify_storage_slots(test_data, storage_slots),
+            vec![true, false, false, true]
+        );
+        let logs = get_logs();
+        assert!(logs.contains(&"Invalid storage proof: truncated proof at depth 2".to_string()));
+    }
+
+    #[test]
+    pub fn test_verify_storage_slots_of_account_without_storage() {
+        let mut test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        // The state trie with the single account of the address, whose storage trie is empty
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&1u64);
+        stream.append(&0u64);
+        stream.append(&EMPTY_TRIE_ROOT.to_vec());
+        stream.append(&EMPTY_CODE_HASH.to_vec());
+        let account_state = stream.out().to_vec();
+        let mut stream = RlpStream::new_list(2);
+        stream.append(
+            &[
+                vec![0x20u8],
+                near_keccak256(&test_data.contract_address).to_vec(),
+            ]
+            .concat(),
+        );
+        stream.append(&account_state);
+        let leaf = stream.out().to_vec();
+        let mut header: BlockHeader = rlp::decode(&test_data.header_data).unwrap();
+        header.state_root = near_keccak256(&leaf).into();
+        test_data.header_data = rlp::encode(&header).to_vec();
+        test_data.account_proof = vec![leaf];
+
+        let empty_slot = StorageSlotProof {
+            key_hash: test_data.storage_key_hash.clone(),
+            value: vec![],
+            proof: vec![],
+        };
+        let mut non_empty_slot = storage_slot(&test_data);
+        non_empty_slot.proof = vec![];
+
+        assert_eq!(
+            verify_storage_slots(test_data, vec![empty_slot, non_empty_slot]),
+            vec![true, false]
+        );
+    }
+
+    #[test]
+    pub fn test_verify_storage_slots_with_wrong_account_proof() {
+        let test_data = get_storage_proof(String::from(
+            "./src/test_data/storageProofWithIncorrectAccountProof.json",
+        ));
+        let storage_slots = vec![storage_slot(&test_data), storage_slot(&test_data)];
+
+        assert_eq!(
+            verify_storage_slots(test_data, storage_slots),
+            vec![false, false]
+        );
+    }
+
+    #[test]
+    pub fn test_on_storage_slots_block_hash() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let block_hash = H256::from([0x11; 32]);
+
+        assert_eq!(
+            contract.on_storage_slots_block_hash(Some(block_hash), block_hash, vec![true, false]),
+            vec![true, false]
+        );
+        assert_eq!(
+            contract.on_storage_slots_block_hash(None, block_hash, vec![true, false]),
+            vec![false, false]
+        );
+    }
 }
@@ -0,0 +1,312 @@
+#[cfg(test)]
+mod tests_transaction_proof {
+    use crate::EthProver;
+    use eth_types::*;
+    use hex::FromHex;
+    use near_sdk::test_utils::get_logs;
+    use near_sdk::PromiseOrValue;
+    use rlp::RlpStream;
+
+    const TO: [u8; 20] = [0x11; 20];
+    const STORAGE_KEY: [u8; 32] = [0x44; 32];
+    const BLOB_HASH: [u8; 32] = [0x55; 32];
+    const CALLDATA: [u8; 8] = [0xa9, 0x05, 0x9c, 0xbb, 0x00, 0x00, 0x00, 0x01];
+
+    use near_sdk::{testing_env, NearToken, VMContext};
+
+    fn get_context(input: Vec<u8>) -> VMContext {
+        VMContext {
+            current_account_id: "alice.near".parse().unwrap(),
+            signer_account_id: "bob.near".parse().unwrap(),
+            signer_account_pk: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap(),
+            predecessor_account_id: "carol.near".parse().unwrap(),
+            input: input.into(),
+            block_index: 0,
+            block_timestamp: 0,
+            epoch_height: 0,
+            account_balance: NearToken::from_near(0),
+            account_locked_balance: NearToken::from_near(0),
+            storage_usage: 0,
+            attached_deposit: NearToken::from_near(0),
+            prepaid_gas: near_sdk::Gas::from_tgas(1_000_000),
+            random_seed: vec![1; 32].try_into().unwrap(),
+            view_config: None,
+            output_data_receivers: vec![],
+        }
+    }
+
+    fn append_common_fields(stream: &mut RlpStream) {
+        stream.append(&21_000u64);
+        stream.append(&TO.to_vec());
+        stream.append(&1_000_000_000_000_000_000u64);
+        stream.append(&CALLDATA.to_vec());
+    }
+
+    fn append_access_list(stream: &mut RlpStream) {
+        stream.begin_list(1);
+        stream.begin_list(2);
+        stream.append(&TO.to_vec());
+        stream.begin_list(1);
+        stream.append(&STORAGE_KEY.to_vec());
+    }
+
+    fn append_signature(stream: &mut RlpStream, v: u64) {
+        stream.append(&v);
+        stream.append(&vec![0x22u8; 32]);
+        stream.append(&vec![0x33u8; 32]);
+    }
+
+    fn typed_transaction(tx_type: u8, stream: RlpStream) -> Vec<u8> {
+        [vec![tx_type], stream.out().to_vec()].concat()
+    }
+
+    /// Returns the header and the proof of the transactions trie with the single transaction
+    fn single_transaction_proof(tx_data: &[u8]) -> (Vec<u8>, Vec<Vec<u8>>) {
+        // The leaf with the `rlp(0)` key, i.e. the transaction index 0
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&vec![0x20u8, 0x80]);
+        stream.append(&tx_data.to_vec());
+        let leaf = stream.out().to_vec();
+
+        let header_data = Vec::from_hex("f9021aa0f779e50b45bc27e4ed236840e5dbcf7afab50beaf553be56bf76da977e10cc73a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d493479452bc44d5378309ee2abf1539bf71de1b7d7be3b5a014c996b6934d7991643669e145b8355c63aa02cbde63d390fcf4e6181d5eea45a079b7e79dc739c31662fe6f25f65bf5a5d14299c7a7aa42c3f75b9fb05474f54ca0e28dc05418692cb7baab7e7f85c1dedb8791c275b797ea3b1ffcaec5ef2aa271b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000903234373439353837313930323034343383890fe68395ba8e82d0d9845dd84a079150505945206e616e6f706f6f6c2e6f7267a0a35425f443452cf94ba4b698b00fd7b3ff4fc671dea3d5cc2dcbedbc3766f45e88af7fec6031063a17").unwrap();
+        let mut header: BlockHeader = rlp::decode(&header_data).unwrap();
+        header.transactions_root = near_keccak256(&leaf).into();
+
+        (rlp::encode(&header).to_vec(), vec![leaf])
+    }
+
+    fn verify_transaction(tx_data: Vec<u8>, proof_tx_data: &[u8]) -> Option<Transaction> {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let (header_data, proof) = single_transaction_proof(proof_tx_data);
+
+        match contract.verify_transaction(0, tx_data, header_data, proof, None, None, true) {
+            PromiseOrValue::Value(transaction) => transaction,
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+    }
+
+    fn assert_common_fields(transaction: &Transaction, tx_data: &[u8]) {
+        assert_eq!(transaction.hash, H256::from(near_keccak256(tx_data)));
+        assert_eq!(transaction.nonce, 7);
+        assert_eq!(transaction.gas_limit.0.as_u64(), 21_000);
+        assert_eq!(transaction.to.unwrap().0.as_bytes(), &TO);
+        assert_eq!(transaction.value.0.as_u64(), 1_000_000_000_000_000_000);
+        assert_eq!(transaction.input, CALLDATA.to_vec());
+    }
+
+    fn assert_access_list(transaction: &Transaction) {
+        assert_eq!(transaction.access_list.len(), 1);
+        assert_eq!(transaction.access_list[0].address.0.as_bytes(), &TO);
+        assert_eq!(
+            transaction.access_list[0].storage_keys[0].0.as_bytes(),
+            &STORAGE_KEY
+        );
+    }
+
+    #[test]
+    pub fn test_verify_legacy_transaction() {
+        let mut stream = RlpStream::new_list(9);
+        stream.append(&7u64);
+        stream.append(&20_000_000_000u64);
+        append_common_fields(&mut stream);
+        // EIP-155 signature of the chain 1
+        append_signature(&mut stream, 37);
+        let tx_data = stream.out().to_vec();
+
+        let transaction = verify_transaction(tx_data.clone(), &tx_data).unwrap();
+        assert_common_fields(&transaction, &tx_data);
+        assert_eq!(transaction.tx_type, LEGACY_TX_TYPE);
+        assert_eq!(transaction.chain_id, Some(1));
+        assert_eq!(transaction.gas_price.unwrap().0.as_u64(), 20_000_000_000);
+        assert_eq!(transaction.max_fee_per_gas, None);
+        assert!(transaction.access_list.is_empty());
+    }
+
+    #[test]
+    pub fn test_verify_legacy_contract_creation() {
+        let mut stream = RlpStream::new_list(9);
+        stream.append(&0u64);
+        stream.append(&20_000_000_000u64);
+        stream.append(&1_000_000u64);
+        stream.append_empty_data();
+        stream.append(&0u64);
+        stream.append(&CALLDATA.to_vec());
+        // Signature without the replay protection
+        append_signature(&mut stream, 27);
+        let tx_data = stream.out().to_vec();
+
+        let transaction = verify_transaction(tx_data.clone(), &tx_data).unwrap();
+        assert_eq!(transaction.chain_id, None);
+        assert_eq!(transaction.to, None);
+        assert_eq!(transaction.input, CALLDATA.to_vec());
+    }
+
+    #[test]
+    pub fn test_verify_access_list_transaction() {
+        let mut stream = RlpStream::new_list(11);
+        stream.append(&5u64);
+        stream.append(&7u64);
+        stream.append(&20_000_000_000u64);
+        append_common_fields(&mut stream);
+        append_access_list(&mut stream);
+        append_signature(&mut stream, 1);
+        let tx_data = typed_transaction(ACCESS_LIST_TX_TYPE, stream);
+
+        let transaction = verify_transaction(tx_data.clone(), &tx_data).unwrap();
+        assert_common_fields(&transaction, &tx_data);
+        assert_access_list(&transaction);
+        assert_eq!(transaction.tx_type, ACCESS_LIST_TX_TYPE);
+        assert_eq!(transaction.chain_id, Some(5));
+        assert_eq!(transaction.gas_price.unwrap().0.as_u64(), 20_000_000_000);
+    }
+
+    #[test]
+    pub fn test_verify_dynamic_fee_transaction() {
+        let mut stream = RlpStream::new_list(12);
+        stream.append(&1u64);
+        stream.append(&7u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&30_000_000_000u64);
+        append_common_fields(&mut stream);
+        append_access_list(&mut stream);
+        append_signature(&mut stream, 0);
+        let tx_data = typed_transaction(DYNAMIC_FEE_TX_TYPE, stream);
+
+        let transaction = verify_transaction(tx_data.clone(), &tx_data).unwrap();
+        assert_common_fields(&transaction, &tx_data);
+        assert_access_list(&transaction);
+        assert_eq!(transaction.tx_type, DYNAMIC_FEE_TX_TYPE);
+        assert_eq!(transaction.chain_id, Some(1));
+        assert_eq!(transaction.gas_price, None);
+        assert_eq!(
+            transaction.max_priority_fee_per_gas.unwrap().0.as_u64(),
+            1_000_000_000
+        );
+        assert_eq!(
+            transaction.max_fee_per_gas.unwrap().0.as_u64(),
+            30_000_000_000
+        );
+    }
+
+    #[test]
+    pub fn test_verify_blob_transaction() {
+        let mut stream = RlpStream::new_list(14);
+        stream.append(&1u64);
+        stream.append(&7u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&30_000_000_000u64);
+        append_common_fields(&mut stream);
+        append_access_list(&mut stream);
+        stream.append(&3_000_000_000u64);
+        stream.begin_list(1);
+        stream.append(&BLOB_HASH.to_vec());
+        append_signature(&mut stream, 1);
+        let tx_data = typed_transaction(BLOB_TX_TYPE, stream);
+
+        let transaction = verify_transaction(tx_data.clone(), &tx_data).unwrap();
+        assert_common_fields(&transaction, &tx_data);
+        assert_eq!(transaction.tx_type, BLOB_TX_TYPE);
+        assert_eq!(
+            transaction.max_fee_per_blob_gas.unwrap().0.as_u64(),
+            3_000_000_000
+        );
+        assert_eq!(transaction.blob_versioned_hashes.len(), 1);
+        assert_eq!(
+            transaction.blob_versioned_hashes[0].0.as_bytes(),
+            &BLOB_HASH
+        );
+    }
+
+    #[test]
+    pub fn test_verify_set_code_transaction() {
+        let mut stream = RlpStream::new_list(13);
+        stream.append(&1u64);
+        stream.append(&7u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&30_000_000_000u64);
+        append_common_fields(&mut stream);
+        append_access_list(&mut stream);
+        stream.begin_list(1);
+        stream.begin_list(6);
+        stream.append(&1u64);
+        stream.append(&TO.to_vec());
+        stream.append(&3u64);
+        append_signature(&mut stream, 1);
+        append_signature(&mut stream, 0);
+        let tx_data = typed_transaction(SET_CODE_TX_TYPE, stream);
+
+        let transaction = verify_transaction(tx_data.clone(), &tx_data).unwrap();
+        assert_common_fields(&transaction, &tx_data);
+        assert_eq!(transaction.tx_type, SET_CODE_TX_TYPE);
+        assert_eq!(transaction.authorization_list.len(), 1);
+        let authorization = &transaction.authorization_list[0];
+        assert_eq!(authorization.chain_id.0.as_u64(), 1);
+        assert_eq!(authorization.address.0.as_bytes(), &TO);
+        assert_eq!(authorization.nonce, 3);
+    }
+
+    #[test]
+    pub fn test_verify_transaction_with_wrong_proof() {
+        let mut stream = RlpStream::new_list(9);
+        stream.append(&7u64);
+        stream.append(&20_000_000_000u64);
+        append_common_fields(&mut stream);
+        append_signature(&mut stream, 37);
+        let tx_data = stream.out().to_vec();
+
+        let mut stream = RlpStream::new_list(9);
+        stream.append(&8u64);
+        stream.append(&20_000_000_000u64);
+        append_common_fields(&mut stream);
+        append_signature(&mut stream, 37);
+        let other_tx_data = stream.out().to_vec();
+
+        assert_eq!(verify_transaction(tx_data, &other_tx_data), None);
+    }
+
+    #[test]
+    pub fn test_verify_transaction_with_unsupported_type() {
+        let mut stream = RlpStream::new_list(12);
+        stream.append(&1u64);
+        stream.append(&7u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&30_000_000_000u64);
+        append_common_fields(&mut stream);
+        append_access_list(&mut stream);
+        append_signature(&mut stream, 0);
+        let tx_data = typed_transaction(0x05, stream);
+
+        assert_eq!(verify_transaction(tx_data.clone(), &tx_data), None);
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.starts_with("Invalid transaction: ")
+                && log.contains("Unsupported transaction type")));
+    }
+
+    #[test]
+    pub fn test_verify_blob_transaction_without_destination() {
+        let mut stream = RlpStream::new_list(14);
+        stream.append(&1u64);
+        stream.append(&7u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&30_000_000_000u64);
+        stream.append(&21_000u64);
+        stream.append_empty_data();
+        stream.append(&0u64);
+        stream.append(&CALLDATA.to_vec());
+        append_access_list(&mut stream);
+        stream.append(&3_000_000_000u64);
+        stream.begin_list(1);
+        stream.append(&BLOB_HASH.to_vec());
+        append_signature(&mut stream, 1);
+        let tx_data = typed_transaction(BLOB_TX_TYPE, stream);
+
+        assert_eq!(verify_transaction(tx_data.clone(), &tx_data), None);
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.contains("The transaction of this type can't create a contract")));
+    }
+}
@@ -0,0 +1,205 @@
+#[cfg(test)]
+mod tests_trie_proof {
+    use crate::{EthProver, ProofError};
+    use eth_types::*;
+    use rlp::RlpStream;
+
+    use near_sdk::{testing_env, NearToken, VMContext};
+
+    const VALUE: [u8; 40] = [0x42; 40];
+
+    fn get_context(input: Vec<u8>) -> VMContext {
+        VMContext {
+            current_account_id: "alice.near".parse().unwrap(),
+            signer_account_id: "bob.near".parse().unwrap(),
+            signer_account_pk: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap(),
+            predecessor_account_id: "carol.near".parse().unwrap(),
+            input: input.into(),
+            block_index: 0,
+            block_timestamp: 0,
+            epoch_height: 0,
+            account_balance: NearToken::from_near(0),
+            account_locked_balance: NearToken::from_near(0),
+            storage_usage: 0,
+            attached_deposit: NearToken::from_near(0),
+            prepaid_gas: near_sdk::Gas::from_tgas(1_000_000),
+            random_seed: vec![1; 32].try_into().unwrap(),
+            view_config: None,
+            output_data_receivers: vec![],
+        }
+    }
+
+    fn leaf_node(value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&vec![0x20u8]);
+        stream.append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    /// Returns the root and the nodes of the trie with the `VALUE` at the `0x01` key and another
+    /// value at the `0x02` key: the shared extension node of the nibble 0, the branch node and
+    /// the leaf node of the nibble 1
+    fn trie() -> (H256, [Vec<u8>; 3]) {
+        let leaf = leaf_node(&VALUE);
+        let other_leaf = leaf_node(&[0x43; 40]);
+
+        let mut stream = RlpStream::new_list(17);
+        for nibble in 0..16 {
+            match nibble {
+                1 => stream.append(&near_keccak256(&leaf).to_vec()),
+                2 => stream.append(&near_keccak256(&other_leaf).to_vec()),
+                _ => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        let branch = stream.out().to_vec();
+
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&vec![0x10u8]);
+        stream.append(&near_keccak256(&branch).to_vec());
+        let extension = stream.out().to_vec();
+
+        (near_keccak256(&extension).into(), [extension, branch, leaf])
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof() {
+        let (root, [extension, branch, leaf]) = trie();
+
+        assert_eq!(
+            EthProver::verify_trie_proof(root, vec![0x01], vec![extension, branch, leaf]),
+            Ok(VALUE.to_vec())
+        );
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof_of_absent_key() {
+        let (root, [extension, branch, _]) = trie();
+
+        assert_eq!(
+            EthProver::verify_trie_proof(root, vec![0x03], vec![extension, branch]),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof_of_absent_key_at_extension_node() {
+        let (root, [extension, branch, _]) = trie();
+
+        // The key diverges from the path of the extension node, so the proof ends there
+        assert_eq!(
+            EthProver::verify_trie_proof(root, vec![0x11], vec![extension.clone()]),
+            Ok(vec![])
+        );
+        assert_eq!(
+            EthProver::verify_trie_proof(root, vec![0x11], vec![extension, branch]),
+            Err(ProofError::ExtraNodes { depth: 0 })
+        );
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof_with_hash_mismatch() {
+        let (root, [extension, branch, leaf]) = trie();
+
+        assert_eq!(
+            EthProver::verify_trie_proof(
+                H256::from([0x11; 32]),
+                vec![0x01],
+                vec![extension.clone(), branch.clone(), leaf]
+            ),
+            Err(ProofError::HashMismatch { depth: 0 })
+        );
+        assert_eq!(
+            EthProver::verify_trie_proof(
+                root,
+                vec![0x01],
+                vec![extension, branch, leaf_node(&[0x43; 40])]
+            ),
+            Err(ProofError::HashMismatch { depth: 2 })
+        );
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof_with_bad_node_arity() {
+        let mut stream = RlpStream::new_list(3);
+        stream.append(&vec![0x01u8]);
+        stream.append(&vec![0x02u8]);
+        stream.append(&vec![0x03u8]);
+        let node = stream.out().to_vec();
+
+        assert_eq!(
+            EthProver::verify_trie_proof(near_keccak256(&node).into(), vec![0x01], vec![node]),
+            Err(ProofError::BadNodeArity { depth: 0, items: 3 })
+        );
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof_with_key_mismatch() {
+        let (root, [extension, branch, leaf]) = trie();
+
+        assert_eq!(
+            EthProver::verify_trie_proof(root, vec![0x01, 0x00], vec![extension, branch, leaf]),
+            Err(ProofError::KeyMismatch { depth: 2 })
+        );
+    }
+
+    #[test]
+    pub fn test_verify_truncated_trie_proof() {
+        let (root, [extension, branch, _]) = trie();
+
+        assert_eq!(
+            EthProver::verify_trie_proof(root, vec![0x01], vec![extension, branch]),
+            Err(ProofError::TruncatedProof { depth: 2 })
+        );
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof_with_extra_nodes() {
+        let (root, [extension, branch, leaf]) = trie();
+
+        assert_eq!(
+            EthProver::verify_trie_proof(
+                root,
+                vec![0x01],
+                vec![extension, branch, leaf.clone(), leaf]
+            ),
+            Err(ProofError::ExtraNodes { depth: 2 })
+        );
+    }
+
+    #[test]
+    pub fn test_verify_trie_proof_with_invalid_node() {
+        let node = vec![0xff];
+
+        assert_eq!(
+            EthProver::verify_trie_proof(near_keccak256(&node).into(), vec![0x01], vec![node]),
+            Err(ProofError::InvalidNode { depth: 0 })
+        );
+    }
+
+    #[test]
+    pub fn test_check_trie_proof() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let (root, [extension, branch, leaf]) = trie();
+
+        assert_eq!(
+            contract.check_trie_proof(
+                root,
+                vec![0x01],
+                vec![extension.clone(), branch.clone(), leaf]
+            ),
+            None
+        );
+        assert_eq!(
+            contract.check_trie_proof(root, vec![0x01], vec![extension, branch]),
+            Some(ProofError::TruncatedProof { depth: 2 })
+        );
+        assert_eq!(
+            ProofError::TruncatedProof { depth: 2 }.to_string(),
+            "truncated proof at depth 2"
+        );
+    }
+}
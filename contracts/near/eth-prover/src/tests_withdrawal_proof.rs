@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests_withdrawal_proof {
+    use crate::EthProver;
+    use eth_types::*;
+    use hex::FromHex;
+    use near_sdk::test_utils::get_logs;
+    use near_sdk::PromiseOrValue;
+    use rlp::RlpStream;
+
+    const ADDRESS: [u8; 20] = [0x11; 20];
+
+    use near_sdk::{testing_env, NearToken, VMContext};
+
+    fn get_context(input: Vec<u8>) -> VMContext {
+        VMContext {
+            current_account_id: "alice.near".parse().unwrap(),
+            signer_account_id: "bob.near".parse().unwrap(),
+            signer_account_pk: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap(),
+            predecessor_account_id: "carol.near".parse().unwrap(),
+            input: input.into(),
+            block_index: 0,
+            block_timestamp: 0,
+            epoch_height: 0,
+            account_balance: NearToken::from_near(0),
+            account_locked_balance: NearToken::from_near(0),
+            storage_usage: 0,
+            attached_deposit: NearToken::from_near(0),
+            prepaid_gas: near_sdk::Gas::from_tgas(1_000_000),
+            random_seed: vec![1; 32].try_into().unwrap(),
+            view_config: None,
+            output_data_receivers: vec![],
+        }
+    }
+
+    fn encode_withdrawal(index: u64, validator_index: u64, amount: u64) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&index);
+        stream.append(&validator_index);
+        stream.append(&ADDRESS.to_vec());
+        stream.append(&amount);
+        stream.out().to_vec()
+    }
+
+    fn leaf(encoded_path: u8, value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&vec![encoded_path]);
+        stream.append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    fn header(withdrawals_root: Option<H256>) -> Vec<u8> {
+        let header_data = Vec::from_hex("f9021aa0f779e50b45bc27e4ed236840e5dbcf7afab50beaf553be56bf76da977e10cc73a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d493479452bc44d5378309ee2abf1539bf71de1b7d7be3b5a014c996b6934d7991643669e145b8355c63aa02cbde63d390fcf4e6181d5eea45a079b7e79dc739c31662fe6f25f65bf5a5d14299c7a7aa42c3f75b9fb05474f54ca0e28dc05418692cb7baab7e7f85c1dedb8791c275b797ea3b1ffcaec5ef2aa271b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000903234373439353837313930323034343383890fe68395ba8e82d0d9845dd84a079150505945206e616e6f706f6f6c2e6f7267a0a35425f443452cf94ba4b698b00fd7b3ff4fc671dea3d5cc2dcbedbc3766f45e88af7fec6031063a17").unwrap();
+        let mut header: BlockHeader = rlp::decode(&header_data).unwrap();
+        // The optional fields are encoded in order, so the base fee precedes the withdrawals root
+        header.base_fee_per_gas = Some(7);
+        header.withdrawals_root = withdrawals_root;
+        rlp::encode(&header).to_vec()
+    }
+
+    /// Returns the header and the proofs of the withdrawals trie with two withdrawals
+    fn withdrawals_proofs(withdrawals: [&[u8]; 2]) -> (Vec<u8>, [Vec<Vec<u8>>; 2]) {
+        // The keys `rlp(0) = 0x80` and `rlp(1) = 0x01` diverge at the first nibble,
+        // so the leaves keep the single remaining nibble of the key
+        let first_leaf = leaf(0x30, withdrawals[0]);
+        let second_leaf = leaf(0x31, withdrawals[1]);
+
+        let mut stream = RlpStream::new_list(17);
+        for nibble in 0..16 {
+            match nibble {
+                0 => stream.append(&near_keccak256(&second_leaf).to_vec()),
+                8 => stream.append(&near_keccak256(&first_leaf).to_vec()),
+                _ => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        let branch = stream.out().to_vec();
+
+        (
+            header(Some(near_keccak256(&branch).into())),
+            [vec![branch.clone(), first_leaf], vec![branch, second_leaf]],
+        )
+    }
+
+    fn verify_withdrawal(
+        withdrawal_position: u64,
+        withdrawal_data: Vec<u8>,
+        header_data: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+    ) -> Option<Withdrawal> {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+
+        match contract.verify_withdrawal(
+            withdrawal_position,
+            withdrawal_data,
+            header_data,
+            proof,
+            None,
+            None,
+            true,
+        ) {
+            PromiseOrValue::Value(withdrawal) => withdrawal,
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+    }
+
+    #[test]
+    pub fn test_verify_withdrawal() {
+        let first = encode_withdrawal(1_000, 42, 1_500_000);
+        let second = encode_withdrawal(1_001, 43, 32_000_000_000);
+        let (header_data, [first_proof, second_proof]) = withdrawals_proofs([&first, &second]);
+
+        let withdrawal = verify_withdrawal(0, first, header_data.clone(), first_proof).unwrap();
+        assert_eq!(withdrawal.index, 1_000);
+        assert_eq!(withdrawal.validator_index, 42);
+        assert_eq!(withdrawal.address.0.as_bytes(), &ADDRESS);
+        assert_eq!(withdrawal.amount, 1_500_000);
+
+        let withdrawal = verify_withdrawal(1, second, header_data, second_proof).unwrap();
+        assert_eq!(withdrawal.index, 1_001);
+        assert_eq!(withdrawal.validator_index, 43);
+        assert_eq!(withdrawal.amount, 32_000_000_000);
+    }
+
+    #[test]
+    pub fn test_verify_withdrawal_with_wrong_proof() {
+        let first = encode_withdrawal(1_000, 42, 1_500_000);
+        let second = encode_withdrawal(1_001, 43, 32_000_000_000);
+        let (header_data, [first_proof, _]) = withdrawals_proofs([&first, &second]);
+
+        assert_eq!(verify_withdrawal(0, second, header_data, first_proof), None);
+    }
+
+    #[test]
+    pub fn test_verify_withdrawal_out_of_bound_height() {
+        let first = encode_withdrawal(1_000, 42, 1_500_000);
+        let second = encode_withdrawal(1_001, 43, 32_000_000_000);
+        let (header_data, [first_proof, _]) = withdrawals_proofs([&first, &second]);
+
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let header: BlockHeader = rlp::decode(&header_data).unwrap();
+        match contract.verify_withdrawal(
+            0,
+            first,
+            header_data,
+            first_proof,
+            Some(header.number + 1),
+            None,
+            true,
+        ) {
+            PromiseOrValue::Value(withdrawal) => assert_eq!(withdrawal, None),
+            PromiseOrValue::Promise(_) => panic!("The bridge call is skipped"),
+        }
+    }
+
+    #[test]
+    pub fn test_verify_withdrawal_before_shapella() {
+        let first = encode_withdrawal(1_000, 42, 1_500_000);
+        let second = encode_withdrawal(1_001, 43, 32_000_000_000);
+        let (_, [first_proof, _]) = withdrawals_proofs([&first, &second]);
+
+        assert_eq!(verify_withdrawal(0, first, header(None), first_proof), None);
+        assert!(get_logs().contains(&"The header has no withdrawals root".to_string()));
+    }
+
+    #[test]
+    pub fn test_verify_withdrawal_with_invalid_data() {
+        let first = encode_withdrawal(1_000, 42, 1_500_000);
+        let second = encode_withdrawal(1_001, 43, 32_000_000_000);
+        let (header_data, [first_proof, _]) = withdrawals_proofs([&first, &second]);
+
+        assert_eq!(
+            verify_withdrawal(0, vec![0x01], header_data, first_proof),
+            None
+        );
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.starts_with("Invalid withdrawal: ")));
+    }
+}
@@ -242,6 +242,15 @@ pub struct LightClientUpdate {
 #[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
 pub struct LightClientUpdates(pub Vec<LightClientUpdate>);
 
+// Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#lightclientbootstrap
+#[derive(Debug, Clone, BorshDeserialize, BorshSchema, BorshSerialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct LightClientBootstrap {
+    pub header: LightClientHeader,
+    pub current_sync_committee: SyncCommittee,
+    pub current_sync_committee_branch: Vec<H256>,
+}
+
 #[derive(Clone, BorshDeserialize, BorshSchema, BorshSerialize, Debug)]
 pub struct LightClientState {
     pub finalized_beacon_header: ExtendedBeaconBlockHeader,
@@ -428,6 +428,22 @@ impl rlp::Encodable for LogEntry {
     }
 }
 
+/// Log entry verified against the receipts root of the block
+#[derive(Default, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct VerifiedLog {
+    /// Address of the contract that emitted the log
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+    pub block_number: u64,
+    pub block_hash: H256,
+    /// Index of the receipt in the block, equal to the index of the transaction
+    pub receipt_index: u64,
+    /// Index of the log in the receipt
+    pub log_index: u64,
+}
+
 // Receipt Header
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -474,6 +490,286 @@ impl From<RlpDeriveReceipt> for Receipt {
     }
 }
 
+// Transaction
+
+pub const LEGACY_TX_TYPE: u8 = 0;
+pub const ACCESS_LIST_TX_TYPE: u8 = 1;
+pub const DYNAMIC_FEE_TX_TYPE: u8 = 2;
+pub const BLOB_TX_TYPE: u8 = 3;
+pub const SET_CODE_TX_TYPE: u8 = 4;
+
+/// Entry of the EIP-2930 access list
+#[derive(Default, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<H256>,
+}
+
+impl rlp::Decodable for AccessListItem {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 2 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        Ok(Self {
+            address: rlp.val_at(0)?,
+            storage_keys: rlp.list_at(1)?,
+        })
+    }
+}
+
+/// EIP-7702 authorization without the signature of the authority
+#[derive(Default, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct Authorization {
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: u64,
+}
+
+impl rlp::Decodable for Authorization {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        // https://eips.ethereum.org/EIPS/eip-7702#set-code-transaction
+        if rlp.item_count()? != 6 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            address: rlp.val_at(1)?,
+            nonce: rlp.val_at(2)?,
+        })
+    }
+}
+
+/// Fields of the legacy or the EIP-2718 typed transaction which don't depend on the sender,
+/// i.e. all the fields except the signature
+#[derive(Default, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct Transaction {
+    /// EIP-2718 transaction type, `LEGACY_TX_TYPE` for the legacy transactions
+    pub tx_type: u8,
+    /// Keccak256 of the encoded transaction
+    pub hash: H256,
+    /// Not set for the legacy transactions without the EIP-155 replay protection
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    /// Set for the legacy and the EIP-2930 transactions
+    pub gas_price: Option<U256>,
+    /// Set for the EIP-1559 based transactions
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Set for the EIP-1559 based transactions
+    pub max_fee_per_gas: Option<U256>,
+    pub gas_limit: U256,
+    /// Not set for the contract creation
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+    /// Set for the EIP-4844 blob transactions
+    pub max_fee_per_blob_gas: Option<U256>,
+    pub blob_versioned_hashes: Vec<H256>,
+    pub authorization_list: Vec<Authorization>,
+}
+
+impl rlp::Decodable for Transaction {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        let view = rlp.as_raw();
+        let hash = near_keccak256(view).into();
+
+        // https://eips.ethereum.org/EIPS/eip-2718
+        match view.first() {
+            None => Err(DecoderError::RlpIsTooShort),
+            // The first byte of the typed transaction envelope is between 0 and 0x7f,
+            // while the legacy transaction is the RLP list
+            Some(&tx_type) if tx_type <= 0x7f => {
+                Self::decode_typed(tx_type, &Rlp::new(&view[1..]), hash)
+            }
+            Some(_) => Self::decode_legacy(rlp, hash),
+        }
+    }
+}
+
+impl Transaction {
+    fn decode_legacy(rlp: &Rlp, hash: H256) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 9 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        // https://eips.ethereum.org/EIPS/eip-155
+        let v: u64 = rlp.val_at(6)?;
+        let chain_id = if v >= 35 { Some((v - 35) / 2) } else { None };
+
+        Ok(Self {
+            tx_type: LEGACY_TX_TYPE,
+            hash,
+            chain_id,
+            nonce: rlp.val_at(0)?,
+            gas_price: Some(rlp.val_at(1)?),
+            gas_limit: rlp.val_at(2)?,
+            to: Self::decode_to(rlp, 3)?,
+            value: rlp.val_at(4)?,
+            input: rlp.val_at(5)?,
+            ..Default::default()
+        })
+    }
+
+    fn decode_typed(tx_type: u8, rlp: &Rlp, hash: H256) -> Result<Self, DecoderError> {
+        // Number of the fields including the signature
+        let fields_count = match tx_type {
+            ACCESS_LIST_TX_TYPE => 11,
+            DYNAMIC_FEE_TX_TYPE => 12,
+            BLOB_TX_TYPE => 14,
+            SET_CODE_TX_TYPE => 13,
+            _ => return Err(DecoderError::Custom("Unsupported transaction type")),
+        };
+        if rlp.item_count()? != fields_count {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        let mut transaction = Self {
+            tx_type,
+            hash,
+            chain_id: Some(rlp.val_at(0)?),
+            nonce: rlp.val_at(1)?,
+            ..Default::default()
+        };
+
+        // The EIP-1559 based transactions replace the gas price with two fee fields
+        let fees_end = if tx_type == ACCESS_LIST_TX_TYPE {
+            transaction.gas_price = Some(rlp.val_at(2)?);
+            3
+        } else {
+            transaction.max_priority_fee_per_gas = Some(rlp.val_at(2)?);
+            transaction.max_fee_per_gas = Some(rlp.val_at(3)?);
+            4
+        };
+
+        transaction.gas_limit = rlp.val_at(fees_end)?;
+        transaction.to = Self::decode_to(rlp, fees_end + 1)?;
+        transaction.value = rlp.val_at(fees_end + 2)?;
+        transaction.input = rlp.val_at(fees_end + 3)?;
+        transaction.access_list = rlp.list_at(fees_end + 4)?;
+
+        match tx_type {
+            // https://eips.ethereum.org/EIPS/eip-4844#blob-transaction
+            BLOB_TX_TYPE => {
+                transaction.max_fee_per_blob_gas = Some(rlp.val_at(fees_end + 5)?);
+                transaction.blob_versioned_hashes = rlp.list_at(fees_end + 6)?;
+            }
+            // https://eips.ethereum.org/EIPS/eip-7702#set-code-transaction
+            SET_CODE_TX_TYPE => {
+                transaction.authorization_list = rlp.list_at(fees_end + 5)?;
+            }
+            _ => {}
+        }
+
+        if transaction.to.is_none() && (tx_type == BLOB_TX_TYPE || tx_type == SET_CODE_TX_TYPE) {
+            return Err(DecoderError::Custom(
+                "The transaction of this type can't create a contract",
+            ));
+        }
+
+        Ok(transaction)
+    }
+
+    fn decode_to(rlp: &Rlp, index: usize) -> Result<Option<Address>, DecoderError> {
+        let to = rlp.at(index)?;
+        if to.is_empty() {
+            Ok(None)
+        } else {
+            to.as_val().map(Some)
+        }
+    }
+}
+
+// Withdrawal
+
+/// EIP-4895 withdrawal of the validator balance to the execution layer
+#[derive(Default, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct Withdrawal {
+    /// Index of the withdrawal across all the blocks
+    pub index: u64,
+    pub validator_index: u64,
+    pub address: Address,
+    /// Amount in Gwei
+    pub amount: u64,
+}
+
+impl rlp::Decodable for Withdrawal {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 4 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        Ok(Self {
+            index: rlp.val_at(0)?,
+            validator_index: rlp.val_at(1)?,
+            address: rlp.val_at(2)?,
+            amount: rlp.val_at(3)?,
+        })
+    }
+}
+
+// Account
+
+/// Keccak-256 hash of the empty code
+pub const EMPTY_CODE_HASH: [u8; 32] = [
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+];
+
+/// Root hash of the empty trie
+pub const EMPTY_TRIE_ROOT: [u8; 32] = [
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+];
+
+/// Account state stored in the state trie
+#[derive(Default, Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct Account {
+    pub nonce: u64,
+    /// Balance in Wei
+    pub balance: U256,
+    pub storage_root: H256,
+    pub code_hash: H256,
+}
+
+impl Account {
+    /// The state of the account that is not in the state trie
+    pub fn empty() -> Self {
+        Self {
+            nonce: 0,
+            balance: U256::default(),
+            storage_root: EMPTY_TRIE_ROOT.into(),
+            code_hash: EMPTY_CODE_HASH.into(),
+        }
+    }
+
+    /// Whether the account has the code, i.e. it is a contract
+    pub fn is_contract(&self) -> bool {
+        self.code_hash != EMPTY_CODE_HASH.into()
+    }
+}
+
+impl rlp::Decodable for Account {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 4 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        Ok(Self {
+            nonce: rlp.val_at(0)?,
+            balance: rlp.val_at(1)?,
+            storage_root: rlp.val_at(2)?,
+            code_hash: rlp.val_at(3)?,
+        })
+    }
+}
+
 pub fn near_sha256(data: &[u8]) -> [u8; 32] {
     let mut buffer = [0u8; 32];
     buffer.copy_from_slice(near_sdk::env::sha256(data).as_slice());
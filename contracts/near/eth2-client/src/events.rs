@@ -0,0 +1,72 @@
+use eth2_utility::consensus::ForkConfig;
+use eth2_utility::types::ContractConfig;
+use near_sdk::{near, AccountId, NearToken};
+
+/// NEP-297 events emitted on the state transitions of the client
+#[near(event_json(standard = "eth2-client"))]
+pub enum Eth2ClientEvent {
+    /// The light client update is committed, either finalized or forced by the DAO
+    #[event_version("1.0.0")]
+    LightClientUpdateAccepted {
+        old_finalized_slot: u64,
+        new_finalized_slot: u64,
+        period: u64,
+        participation: u64,
+        forced: bool,
+    },
+    /// The sync committee of `period` became the current one
+    #[event_version("1.0.0")]
+    SyncCommitteeRotated { period: u64 },
+    /// The submitted headers are connected to the finalized chain,
+    /// the blocks from `start_block` to `end_block` inclusive are finalized
+    #[event_version("1.0.0")]
+    HeadersBatchFinalized {
+        start_block: u64,
+        end_block: u64,
+        submitter: AccountId,
+    },
+    /// The blocks from `start_block` inclusive to `end_block` exclusive are garbage collected
+    #[event_version("1.0.0")]
+    GcExecuted {
+        start_block: u64,
+        end_block: u64,
+        gc_target: u64,
+    },
+    /// The `parameter` of the config is updated by the DAO
+    #[event_version("1.0.0")]
+    ConfigUpdated {
+        parameter: String,
+        config: ContractConfig,
+    },
+    /// The fork is appended to the fork schedule by the DAO
+    #[event_version("1.0.0")]
+    ForkAdded { fork: ForkConfig },
+    /// Two validly signed updates finalize different headers at the same slot,
+    /// the submissions are halted
+    #[event_version("1.0.0")]
+    ConflictingUpdatesReported { slot: u64, reporter: AccountId },
+    /// The DAO resumed the submissions halted by the conflicting updates
+    #[event_version("1.0.0")]
+    SubmissionsResumed { by: AccountId },
+    /// The state is upgraded from the `from_version` layout to the `to_version` one
+    #[event_version("1.0.0")]
+    StateMigrated { from_version: u32, to_version: u32 },
+    /// The DAO added `amount` to the reward pool
+    #[event_version("1.0.0")]
+    RewardPoolFunded {
+        amount: NearToken,
+        balance: NearToken,
+    },
+    /// The DAO withdrew `amount` from the reward pool
+    #[event_version("1.0.0")]
+    RewardPoolWithdrawn {
+        amount: NearToken,
+        balance: NearToken,
+    },
+    /// The submitter claimed the accrued rewards
+    #[event_version("1.0.0")]
+    RewardsClaimed {
+        account_id: AccountId,
+        amount: NearToken,
+    },
+}
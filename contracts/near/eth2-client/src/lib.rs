@@ -11,9 +11,10 @@ use eth2_utility::consensus::*;
 use eth2_utility::types::*;
 use eth_types::eth2::*;
 use eth_types::{BlockHeader, H256};
-use near_sdk::collections::{LazyOption, LookupMap};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap};
 use near_sdk::{
-    env, near, require, AccountId, BorshStorageKey, PanicOnDefault, Promise, PublicKey,
+    env, near, require, AccountId, BorshStorageKey, Gas, NearToken, PanicOnDefault, Promise,
+    PublicKey,
 };
 use omni_utils::macros::trusted_relayer;
 use tree_hash::TreeHash;
@@ -23,10 +24,30 @@ use amcl::bls381::ecp::ECP;
 use amcl::bls381::fp2::FP2;
 use amcl::bls381::hash_to_curve::hash_to_field_fp2;
 
+use events::Eth2ClientEvent;
+
+mod events;
 mod migrate;
 #[cfg(test)]
 mod tests;
 
+/// Default number of execution blocks garbage collected within a single header submission on top
+/// of the number of the submitted headers
+pub const DEFAULT_MAX_GC_ENTRIES_PER_CALL: u64 = 300;
+
+/// Maximum number of block hashes returned by a single `block_hashes_range` call
+pub const MAX_BLOCK_HASHES_RANGE_LIMIT: u64 = 1000;
+
+/// Number of the latest finalized beacon state roots kept for the historical block proofs.
+/// The `historical_summaries` of a recent state cover all the older blocks.
+pub const MAX_FINALIZED_BEACON_STATE_ROOTS: u64 = 1024;
+
+/// Maximum number of accounts whose submissions are counted and rewarded.
+/// The storage of the stats is paid by the contract, so the number of entries is capped.
+pub const MAX_TRACKED_SUBMITTERS: u64 = 100;
+
+const ON_REWARD_TRANSFER_GAS: Gas = Gas::from_tgas(5);
+
 #[derive(BorshSerialize, BorshStorageKey)]
 enum StorageKey {
     FinalizedExecutionBlocks,
@@ -35,6 +56,15 @@ enum StorageKey {
     FinalizedExecutionHeader,
     CurrentSyncCommittee,
     NextSyncCommittee,
+    FinalizedExecutionRoots,
+    BestValidUpdate,
+    ConflictingUpdatesEvidence,
+    FinalizedBeaconStateRoots,
+    FinalizedExecutionTimestamps,
+    CurrentSyncCommitteeKeys,
+    NextSyncCommitteeKeys,
+    SubmitterStats,
+    FinalizedBeaconStateSlots,
 }
 
 #[derive(AccessControlRole, Deserialize, Serialize, Copy, Clone)]
@@ -76,8 +106,6 @@ pub struct Eth2Client {
     /// It is desirable that this number is larger than 7 days' worth of headers, which is roughly
     /// 51k Ethereum blocks. So this number should be 51k in production.
     hashes_gc_threshold: u64,
-    /// Network. e.g. mainnet, goerli
-    network: Network,
     /// Hashes of the finalized execution blocks mapped to their numbers. Stores up to `hashes_gc_threshold` entries.
     /// Execution block number -> execution block hash
     finalized_execution_blocks: LookupMap<u64, H256>,
@@ -90,6 +118,62 @@ pub struct Eth2Client {
     unfinalized_head_execution_header: Option<ExecutionHeaderInfo>,
     unfinalized_tail_execution_header: Option<ExecutionHeaderInfo>,
     trusted_blocks_submitter: Option<AccountId>,
+    /// Whether the client stores the trie roots of the submitted execution blocks
+    store_execution_roots: bool,
+    /// Roots of the finalized execution blocks mapped to their numbers. Populated only while
+    /// `store_execution_roots` is enabled and garbage collected together with the block hashes.
+    /// Execution block number -> execution block roots
+    finalized_execution_roots: LookupMap<u64, ExecutionBlockRoots>,
+    /// All the execution blocks below this number are already garbage collected
+    gc_cursor: u64,
+    /// All the execution blocks below this number should be garbage collected
+    gc_target: u64,
+    /// Maximum number of execution blocks garbage collected within a single header submission on
+    /// top of the number of the submitted headers, i.e. the margin to catch up the gc backlog
+    max_gc_entries_per_call: u64,
+    /// Genesis parameters and fork schedule of the network. The known networks are
+    /// identified by the genesis validators root, so the network isn't stored separately.
+    network_config: NetworkConfig,
+    /// Number of slots without finality after which the DAO can force the best valid update.
+    /// The force update is disabled if not set
+    force_update_timeout: Option<u64>,
+    /// The best non-finalized update by the `is_better_update` rule of the consensus spec seen
+    /// since the last finalized update
+    best_valid_update: LazyOption<LightClientUpdate>,
+    /// The reported pair of conflicting updates, kept until the DAO clears it
+    conflicting_updates_evidence: LazyOption<ConflictingUpdatesEvidence>,
+    /// Whether the header and update submissions are halted by the reported conflicting updates.
+    /// Unlike the pause of the plugin, it's set without any role and cleared only by the DAO
+    submissions_halted: bool,
+    /// Unfinalized headers left by the v0.1.0 layout, removed in batches by the `gc_step`
+    unfinalized_headers_v1: Option<UnorderedMap<H256, ExecutionHeaderInfo>>,
+    /// State roots of the finalized beacon blocks, used to verify the historical block proofs.
+    /// Stores up to `MAX_FINALIZED_BEACON_STATE_ROOTS` entries, the forced headers are skipped.
+    /// Beacon block slot -> beacon state root
+    finalized_beacon_state_roots: LookupMap<u64, H256>,
+    /// Slots of the stored beacon state roots in the insertion order, used to garbage collect
+    /// the oldest state roots.
+    /// Insertion number -> beacon block slot
+    finalized_beacon_state_slots: LookupMap<u64, u64>,
+    /// Number of the beacon state roots stored since the initialization
+    finalized_beacon_state_roots_count: u64,
+    /// Timestamps of the finalized execution blocks, garbage collected together with the block hashes.
+    /// Execution block number -> execution block timestamp
+    finalized_execution_timestamps: LookupMap<u64, u64>,
+    /// Decompressed public keys of the current sync committee
+    current_sync_committee_keys: LazyOption<DecompressedSyncCommittee>,
+    /// Decompressed public keys of the next sync committee
+    next_sync_committee_keys: LazyOption<DecompressedSyncCommittee>,
+    /// Accepted submissions of the relayers.
+    /// Submitter account -> submission counters and rewards
+    submitter_stats: LookupMap<AccountId, SubmitterStats>,
+    /// Number of the accounts in `submitter_stats`, up to `MAX_TRACKED_SUBMITTERS`
+    tracked_submitters: u64,
+    /// Reward accrued to the submitter of each accepted light client update.
+    /// The rewards are disabled if not set
+    light_client_update_reward: Option<NearToken>,
+    /// Part of the contract balance funded by the DAO and not accrued to the submitters yet
+    reward_pool_balance: NearToken,
 }
 
 #[trusted_relayer(
@@ -115,6 +199,11 @@ impl Eth2Client {
             );
         }
 
+        let network_config = NetworkConfig::new(&args.network);
+        if let Err(err) = network_config.validate() {
+            env::panic_str(&err);
+        }
+
         let finalized_execution_header_hash = args.finalized_execution_header.calculate_hash();
 
         require!(
@@ -135,7 +224,6 @@ impl Eth2Client {
             validate_updates: args.validate_updates,
             verify_bls_signatures: args.verify_bls_signatures,
             hashes_gc_threshold: args.hashes_gc_threshold,
-            network: args.network,
             finalized_execution_blocks: LookupMap::new(StorageKey::FinalizedExecutionBlocks),
             finalized_beacon_header: args.finalized_beacon_header,
             finalized_execution_header: LazyOption::new(
@@ -154,14 +242,92 @@ impl Eth2Client {
             unfinalized_head_execution_header: None,
             unfinalized_tail_execution_header: None,
             trusted_blocks_submitter: None,
+            store_execution_roots: false,
+            finalized_execution_roots: LookupMap::new(StorageKey::FinalizedExecutionRoots),
+            gc_cursor: args.finalized_execution_header.number,
+            gc_target: args.finalized_execution_header.number,
+            max_gc_entries_per_call: DEFAULT_MAX_GC_ENTRIES_PER_CALL,
+            network_config,
+            force_update_timeout: None,
+            best_valid_update: LazyOption::new(StorageKey::BestValidUpdate, None),
+            conflicting_updates_evidence: LazyOption::new(
+                StorageKey::ConflictingUpdatesEvidence,
+                None,
+            ),
+            submissions_halted: false,
+            unfinalized_headers_v1: None,
+            finalized_beacon_state_roots: LookupMap::new(StorageKey::FinalizedBeaconStateRoots),
+            finalized_beacon_state_slots: LookupMap::new(StorageKey::FinalizedBeaconStateSlots),
+            finalized_beacon_state_roots_count: 0,
+            finalized_execution_timestamps: LookupMap::new(
+                StorageKey::FinalizedExecutionTimestamps,
+            ),
+            current_sync_committee_keys: LazyOption::new(
+                StorageKey::CurrentSyncCommitteeKeys,
+                Some(&Self::decompress_sync_committee(
+                    &args.current_sync_committee,
+                )),
+            ),
+            next_sync_committee_keys: LazyOption::new(
+                StorageKey::NextSyncCommitteeKeys,
+                Some(&Self::decompress_sync_committee(&args.next_sync_committee)),
+            ),
+            submitter_stats: LookupMap::new(StorageKey::SubmitterStats),
+            tracked_submitters: 0,
+            light_client_update_reward: None,
+            reward_pool_balance: NearToken::from_yoctonear(0),
         };
 
         contract.finalized_execution_blocks.insert(
             &args.finalized_execution_header.number,
             &finalized_execution_header_hash,
         );
+        contract.finalized_execution_timestamps.insert(
+            &args.finalized_execution_header.number,
+            &args.finalized_execution_header.timestamp,
+        );
+        contract.store_finalized_beacon_state_root(
+            contract.finalized_beacon_header.header.slot,
+            contract.finalized_beacon_header.header.state_root,
+        );
 
         contract.acl_init_super_admin(env::predecessor_account_id());
+        migrate::write_state_version();
+        contract
+    }
+
+    /// Initializes the client from the `LightClientBootstrap` of the trusted checkpoint.
+    /// The current sync committee is verified against the trusted block root and the next sync
+    /// committee is taken from the update signed by the current sync committee.
+    #[init]
+    #[private]
+    pub fn init_from_bootstrap(#[serializer(borsh)] args: BootstrapInitInput) -> Self {
+        let bootstrap = args.bootstrap;
+        require!(
+            H256(bootstrap.header.beacon.tree_hash_root().0.into()) == args.trusted_block_root,
+            "The bootstrap header doesn't match the trusted block root"
+        );
+
+        let next_sync_committee = args
+            .update
+            .next_sync_committee
+            .clone()
+            .unwrap_or_else(|| env::panic_str("The sync committee update is missed"));
+
+        let contract = Self::init(InitInput {
+            network: args.network,
+            finalized_execution_header: args.finalized_execution_header,
+            finalized_beacon_header: bootstrap.header.clone().into(),
+            current_sync_committee: bootstrap.current_sync_committee.clone(),
+            next_sync_committee,
+            validate_updates: args.validate_updates,
+            verify_bls_signatures: true,
+            hashes_gc_threshold: args.hashes_gc_threshold,
+            trusted_signer: args.trusted_signer,
+        });
+
+        contract.verify_bootstrap(&bootstrap);
+        contract.verify_bootstrap_update(&args.update);
         contract
     }
 
@@ -176,15 +342,171 @@ impl Eth2Client {
         self.finalized_execution_header.get().unwrap().block_number
     }
 
-    /// Returns finalized execution block hash
+    /// Returns finalized execution block hash. Returns `None` for the blocks outside of the
+    /// `hashes_gc_threshold` window, even if the gc hasn't removed them yet.
     #[result_serializer(borsh)]
     pub fn block_hash_safe(&self, #[serializer(borsh)] block_number: u64) -> Option<H256> {
-        if block_number > self.finalized_execution_header.get().unwrap().block_number {
+        if block_number > self.finalized_execution_header.get().unwrap().block_number
+            || block_number < self.oldest_provable_block_number()
+        {
             return None;
         }
         self.finalized_execution_blocks.get(&block_number)
     }
 
+    /// Returns the hashes of the finalized execution blocks from `from` inclusive to `from + limit`
+    /// exclusive, skipping the blocks which aren't stored. The `limit` is capped by
+    /// `MAX_BLOCK_HASHES_RANGE_LIMIT`, so the next page starts from `from + limit`.
+    #[result_serializer(borsh)]
+    pub fn block_hashes_range(
+        &self,
+        #[serializer(borsh)] from: u64,
+        #[serializer(borsh)] limit: u64,
+    ) -> Vec<(u64, H256)> {
+        let (lowest, highest) = self.finalized_range();
+        let end = from
+            .saturating_add(limit.min(MAX_BLOCK_HASHES_RANGE_LIMIT))
+            .min(highest + 1);
+
+        (from.max(lowest)..end)
+            .filter_map(|block_number| {
+                self.finalized_execution_blocks
+                    .get(&block_number)
+                    .map(|block_hash| (block_number, block_hash))
+            })
+            .collect()
+    }
+
+    /// Returns the lowest and the highest numbers of the stored finalized execution blocks
+    #[result_serializer(borsh)]
+    pub fn finalized_range(&self) -> (u64, u64) {
+        // The blocks above the oldest provable block are missing only if they were garbage
+        // collected before the gc cursor was introduced, so they all precede the stored blocks
+        let highest = self.finalized_execution_header.get().unwrap().block_number;
+        let mut lowest = self.oldest_provable_block_number();
+        let mut high = highest;
+        while lowest < high {
+            let mid = lowest + (high - lowest) / 2;
+            if self.finalized_execution_blocks.get(&mid).is_some() {
+                high = mid;
+            } else {
+                lowest = mid + 1;
+            }
+        }
+
+        (lowest, highest)
+    }
+
+    /// Returns the trie roots and timestamp of the finalized execution block.
+    /// Only available for blocks submitted while `store_execution_roots` is enabled.
+    #[result_serializer(borsh)]
+    pub fn execution_block_roots_safe(
+        &self,
+        #[serializer(borsh)] block_number: u64,
+    ) -> Option<ExecutionBlockRoots> {
+        if block_number > self.finalized_execution_header.get().unwrap().block_number
+            || block_number < self.oldest_provable_block_number()
+        {
+            return None;
+        }
+        self.finalized_execution_roots.get(&block_number)
+    }
+
+    /// Returns the state root of the finalized beacon block
+    #[result_serializer(borsh)]
+    pub fn finalized_beacon_state_root(&self, #[serializer(borsh)] slot: u64) -> Option<H256> {
+        self.finalized_beacon_state_roots.get(&slot)
+    }
+
+    /// Returns the hash of the execution block proven by the historical proof against the stored
+    /// state root of the finalized beacon block at `proof.state_slot`. Allows to verify the blocks
+    /// which are already garbage collected. Returns `None` if the proof is invalid.
+    #[result_serializer(borsh)]
+    pub fn historical_block_hash_safe(
+        &self,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] proof: HistoricalBlockProof,
+    ) -> Option<H256> {
+        let state_root = self.finalized_beacon_state_roots.get(&proof.state_slot)?;
+        if proof.header.execution.block_number != block_number
+            || !self
+                .network_config
+                .is_valid_historical_block_proof(&proof, state_root)
+        {
+            return None;
+        }
+
+        Some(proof.header.execution.block_hash)
+    }
+
+    /// Verifies the Merkle branch of the leaf at the generalized index against the state root of
+    /// the finalized beacon block at `slot`. Returns `false` if the state root isn't stored.
+    #[result_serializer(borsh)]
+    pub fn verify_beacon_state_field(
+        &self,
+        #[serializer(borsh)] slot: u64,
+        #[serializer(borsh)] gindex: u64,
+        #[serializer(borsh)] leaf: H256,
+        #[serializer(borsh)] branch: Vec<H256>,
+    ) -> bool {
+        self.finalized_beacon_state_roots
+            .get(&slot)
+            .is_some_and(|state_root| {
+                verify_merkle_proof_by_gindex(leaf, &branch, gindex, state_root)
+            })
+    }
+
+    /// Returns the generalized indices of the `BeaconState` fields at `slot`
+    #[result_serializer(borsh)]
+    pub fn get_beacon_state_gindices(&self, #[serializer(borsh)] slot: u64) -> Vec<(String, u64)> {
+        self.network_config
+            .get_fork_by_slot(slot)
+            .map(|fork| fork.beacon_state_gindices())
+            .unwrap_or_default()
+    }
+
+    /// Returns the timestamp of the finalized execution block
+    #[result_serializer(borsh)]
+    pub fn block_timestamp(&self, #[serializer(borsh)] block_number: u64) -> Option<u64> {
+        if block_number > self.finalized_execution_header.get().unwrap().block_number
+            || block_number < self.oldest_provable_block_number()
+        {
+            return None;
+        }
+        self.finalized_execution_timestamps.get(&block_number)
+    }
+
+    /// Returns the number of the latest finalized execution block with the timestamp lower than
+    /// or equal to `timestamp`. Returns `None` if the timestamp is older than the earliest block
+    /// which is not garbage collected.
+    #[result_serializer(borsh)]
+    pub fn block_number_by_timestamp(&self, #[serializer(borsh)] timestamp: u64) -> Option<u64> {
+        // The blocks without the timestamp are garbage collected or submitted before the
+        // timestamps were recorded, so they all precede the blocks with the timestamp
+        let is_not_later = |block_number: u64| {
+            self.finalized_execution_timestamps
+                .get(&block_number)
+                .is_none_or(|block_timestamp| block_timestamp <= timestamp)
+        };
+
+        let mut low = self.oldest_provable_block_number();
+        let mut high = self.finalized_execution_header.get().unwrap().block_number;
+        if !is_not_later(low) {
+            return None;
+        }
+
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if is_not_later(mid) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        self.finalized_execution_timestamps.get(&low).map(|_| low)
+    }
+
     /// Checks if the execution header is already submitted.
     #[result_serializer(borsh)]
     pub fn is_known_execution_header(&self, block_number: u64) -> bool {
@@ -245,197 +567,751 @@ impl Eth2Client {
             self.validate_light_client_update(&update);
         }
 
-        self.commit_light_client_update(update);
+        self.commit_light_client_update(update, false);
+        self.record_submission(1, 0);
     }
 
-    #[result_serializer(borsh)]
-    #[trusted_relayer(bypass_roles(Role::DAO, Role::UnrestrictedSubmitExecutionHeader))]
+    /// Submits a batch of light client updates ordered by the finalized slot, which allows to
+    /// catch up several sync committee periods in a single transaction. Each update should be
+    /// within the period of the previous update or the next one, so the sync committees are
+    /// rotated period by period. The batch can't move the finalized execution block further than
+    /// `hashes_gc_threshold`, otherwise the gc of the header submission can't be applied.
+    #[trusted_relayer(bypass_roles(Role::DAO, Role::UnrestrictedSubmitLightClientUpdate))]
     #[pause(except(roles(Role::DAO)))]
-    pub fn submit_execution_header(&mut self, #[serializer(borsh)] block_header: BlockHeader) {
-        if let Some(trusted_blocks_submitter) = &self.trusted_blocks_submitter {
-            require!(
-                &env::predecessor_account_id() == trusted_blocks_submitter,
-                "Eth-client is deployed as trust mode, only trusted_blocks_submitter can submit blocks"
-            );
-        }
-
-        require!(
-            self.client_mode == ClientMode::SubmitHeader,
-            "Client is not in SubmitHeader mode"
-        );
-
-        let block_hash = block_header.calculate_hash();
-        let expected_block_hash = self
-            .unfinalized_tail_execution_header
-            .as_ref()
-            .map(|header| header.parent_hash)
-            .unwrap_or(self.finalized_beacon_header.execution_block_hash);
+    pub fn submit_beacon_chain_light_client_updates(
+        &mut self,
+        #[serializer(borsh)] updates: Vec<LightClientUpdate>,
+    ) {
+        self.is_light_client_update_allowed();
+        require!(!updates.is_empty(), "The updates batch is empty");
+
+        let batch_blocks_count = updates
+            .last()
+            .unwrap()
+            .finalized_header
+            .execution
+            .block_number
+            .saturating_sub(self.last_block_number());
         require!(
-            block_hash == expected_block_hash,
+            batch_blocks_count <= self.hashes_gc_threshold,
             format!(
-                "The expected block hash is {:#?} but got {:#?}.",
-                expected_block_hash, block_hash
+                "The updates batch finalizes {} execution blocks, but the `hashes_gc_threshold` is {}",
+                batch_blocks_count, self.hashes_gc_threshold
             )
         );
 
-        let insert_result = self
-            .finalized_execution_blocks
-            .insert(&block_header.number, &block_hash);
+        let updates_count = updates.len() as u64;
+        for update in updates {
+            if self.validate_updates {
+                self.validate_light_client_update(&update);
+            }
+
+            self.commit_light_client_update(update, false);
+        }
+        self.record_submission(updates_count, 0);
+    }
 
+    /// Submits a non-finalized update signed by the current or the next sync committee while the
+    /// finality is stalled. The update is kept if it's better than the best valid update by the
+    /// `is_better_update` rule of the consensus spec, so it can be forced by the DAO after
+    /// `force_update_timeout`. The update can't be signed later than the current slot of the
+    /// network. Returns whether the update became the best valid update.
+    #[result_serializer(borsh)]
+    #[trusted_relayer(bypass_roles(Role::DAO, Role::UnrestrictedSubmitLightClientUpdate))]
+    #[pause(except(roles(Role::DAO)))]
+    pub fn submit_best_valid_update(
+        &mut self,
+        #[serializer(borsh)] update: LightClientUpdate,
+    ) -> bool {
         require!(
-            insert_result.is_none(),
-            format!("The block {:#?} already submitted!", &block_hash)
+            self.force_update_timeout.is_some(),
+            "The force update is disabled"
         );
+        self.is_light_client_update_allowed();
 
-        let finalized_execution_header = self.finalized_execution_header.get().unwrap();
-        // Apply gc
-        if let Some(diff_between_unfinalized_head_and_tail) =
-            self.get_diff_between_unfinalized_head_and_tail()
-        {
-            let header_number_to_remove = (finalized_execution_header.block_number
-                + diff_between_unfinalized_head_and_tail)
-                .saturating_sub(self.hashes_gc_threshold);
+        // The signing root doesn't cover the signature slot, so it's bounded by the block time
+        let current_slot = self.current_slot();
+        require!(
+            update.signature_slot <= current_slot,
+            format!(
+                "The signature slot {} is ahead of the current slot {}",
+                update.signature_slot, current_slot
+            )
+        );
 
-            require!(
-                header_number_to_remove < finalized_execution_header.block_number,
-                "The `hashes_gc_threshold` is not enough to be able to apply gc correctly"
-            );
+        if self.validate_updates {
+            self.validate_best_valid_update(&update);
+        } else {
+            Self::verify_min_sync_committee_participation(&update);
+        }
 
-            if header_number_to_remove > 0 {
-                self.gc_finalized_execution_blocks(header_number_to_remove);
-            }
+        let is_better = self
+            .best_valid_update
+            .get()
+            .is_none_or(|best_update| Self::is_better_update(&update, &best_update));
+        if is_better {
+            self.best_valid_update.set(&update);
         }
 
-        if block_header.number == finalized_execution_header.block_number + 1 {
-            let finalized_execution_header_hash = self
-                .finalized_execution_blocks
-                .get(&finalized_execution_header.block_number)
-                .unwrap();
-            require!(
-                block_header.parent_hash == finalized_execution_header_hash,
-                "The chain cannot be closed"
-            );
+        is_better
+    }
 
-            #[cfg(feature = "logs")]
-            env::log_str(
-                format!(
-                    "Current finalized block number: {}, New finalized block number: {}",
-                    finalized_execution_header.block_number,
-                    self.unfinalized_head_execution_header
-                        .as_ref()
-                        .unwrap()
-                        .block_number
-                )
-                .as_str(),
-            );
+    /// Applies the best valid update while the finality is stalled, following the
+    /// `process_light_client_store_force_update` of the consensus spec: the attested header is
+    /// used as the finalized one unless the update finalizes a newer header. Allowed only after
+    /// no finalized update was accepted for `force_update_timeout` slots of the block time.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn force_update(&mut self) {
+        let force_update_timeout = self
+            .force_update_timeout
+            .unwrap_or_else(|| env::panic_str("The force update is disabled"));
+        require!(
+            self.client_mode == ClientMode::SubmitLightClientUpdate,
+            "Client is not in SubmitLightClientUpdate mode"
+        );
 
-            self.finalized_execution_header
-                .set(self.unfinalized_head_execution_header.as_ref().unwrap());
-            self.unfinalized_tail_execution_header = None;
-            self.unfinalized_head_execution_header = None;
-            self.client_mode = ClientMode::SubmitLightClientUpdate;
-        } else {
-            let block_info = ExecutionHeaderInfo {
-                parent_hash: block_header.parent_hash,
-                block_number: block_header.number,
-                submitter: env::predecessor_account_id(),
-            };
+        let finalized_slot = self.finalized_beacon_header.header.slot;
+        let current_slot = self.current_slot();
+        require!(
+            current_slot > finalized_slot + force_update_timeout,
+            format!(
+                "The force update is allowed after the slot {}, the current slot is {}",
+                finalized_slot + force_update_timeout,
+                current_slot
+            )
+        );
 
-            if self.unfinalized_head_execution_header.is_none() {
-                self.unfinalized_head_execution_header = Some(block_info.clone());
-            }
-            self.unfinalized_tail_execution_header = Some(block_info);
+        let mut update = self
+            .best_valid_update
+            .get()
+            .unwrap_or_else(|| env::panic_str("There is no best valid update"));
+
+        if !Self::is_finality_update(&update)
+            || update.finalized_header.beacon.slot <= finalized_slot
+        {
+            update.finalized_header = update.attested_header.clone();
         }
 
         #[cfg(feature = "logs")]
         env::log_str(
             format!(
-                "Submitted header number {}, hash {:#?}",
-                block_header.number, block_hash
+                "Force update to the slot {}",
+                update.finalized_header.beacon.slot
             )
             .as_str(),
         );
+
+        self.commit_light_client_update(update, true);
+    }
+
+    /// Reports two updates signed by the stored sync committees which finalize different headers
+    /// at the same slot. Such a conflict is only possible if the sync committee is malicious,
+    /// so the submissions are halted and the evidence is recorded for the DAO.
+    /// Can be called by anyone while no evidence is recorded.
+    pub fn report_conflicting_update(
+        &mut self,
+        #[serializer(borsh)] first_update: LightClientUpdate,
+        #[serializer(borsh)] second_update: LightClientUpdate,
+    ) {
+        require!(
+            self.conflicting_updates_evidence.is_none(),
+            "The conflicting updates are already reported"
+        );
+        let slot = first_update.finalized_header.beacon.slot;
+        require!(
+            slot == second_update.finalized_header.beacon.slot
+                && first_update.finalized_header.beacon.tree_hash_root()
+                    != second_update.finalized_header.beacon.tree_hash_root(),
+            "The updates don't finalize different headers at the same slot"
+        );
+
+        self.verify_signed_finalized_update(&first_update);
+        self.verify_signed_finalized_update(&second_update);
+
+        self.submissions_halted = true;
+        let reporter = env::predecessor_account_id();
+        self.conflicting_updates_evidence
+            .set(&ConflictingUpdatesEvidence {
+                first_update,
+                second_update,
+                reporter: reporter.clone(),
+                reported_at: env::block_height(),
+            });
+
+        Eth2ClientEvent::ConflictingUpdatesReported { slot, reporter }.emit();
+    }
+
+    /// Resumes the submissions halted by the reported conflicting updates. The evidence is kept,
+    /// so the same updates can't be reported again until the DAO clears it.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn resume_submissions(&mut self) {
+        require!(self.submissions_halted, "The submissions aren't halted");
+        self.submissions_halted = false;
+        Eth2ClientEvent::SubmissionsResumed {
+            by: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Removes the recorded conflicting updates, so a new conflict can be reported
+    #[access_control_any(roles(Role::DAO))]
+    pub fn clear_conflicting_updates_evidence(&mut self) {
+        require!(
+            self.conflicting_updates_evidence.is_some(),
+            "There is no conflicting updates evidence"
+        );
+        self.conflicting_updates_evidence.remove();
+    }
+
+    /// Returns the reported pair of conflicting updates
+    #[result_serializer(borsh)]
+    pub fn get_conflicting_updates_evidence(&self) -> Option<ConflictingUpdatesEvidence> {
+        self.conflicting_updates_evidence.get()
+    }
+
+    /// Returns the non-finalized update which can be forced after the timeout
+    #[result_serializer(borsh)]
+    pub fn get_best_valid_update(&self) -> Option<LightClientUpdate> {
+        self.best_valid_update.get()
+    }
+
+    #[result_serializer(borsh)]
+    #[trusted_relayer(bypass_roles(Role::DAO, Role::UnrestrictedSubmitExecutionHeader))]
+    #[pause(except(roles(Role::DAO)))]
+    pub fn submit_execution_header(&mut self, #[serializer(borsh)] block_header: BlockHeader) {
+        self.is_execution_header_submission_allowed();
+        self.process_execution_headers(std::slice::from_ref(&block_header));
+        self.record_submission(0, 1);
+    }
+
+    /// Submits a batch of execution headers ordered from the tail to the head,
+    /// i.e. each header must be the parent of the previously submitted one.
+    #[result_serializer(borsh)]
+    #[trusted_relayer(bypass_roles(Role::DAO, Role::UnrestrictedSubmitExecutionHeader))]
+    #[pause(except(roles(Role::DAO)))]
+    pub fn submit_execution_headers(
+        &mut self,
+        #[serializer(borsh)] block_headers: Vec<BlockHeader>,
+    ) {
+        self.is_execution_header_submission_allowed();
+        require!(!block_headers.is_empty(), "The headers batch is empty");
+        self.process_execution_headers(&block_headers);
+        self.record_submission(0, block_headers.len() as u64);
+    }
+
+    /// Garbage collects at most `max_entries` execution blocks that fell out of the
+    /// `hashes_gc_threshold` window, after the unfinalized headers left by the v0.1.0 layout.
+    /// Can be called by anyone to process the gc backlog which is not covered by the gc applied
+    /// on the header submission.
+    /// Returns the number of entries left to process.
+    #[result_serializer(borsh)]
+    pub fn gc_step(&mut self, max_entries: u64) -> u64 {
+        let removed_headers = self.gc_unfinalized_headers_v1(max_entries);
+        self.gc_finalized_execution_blocks(max_entries - removed_headers);
+        let headers_left = self
+            .unfinalized_headers_v1
+            .as_ref()
+            .map_or(0, |unfinalized_headers| unfinalized_headers.len());
+        self.gc_target.saturating_sub(self.gc_cursor) + headers_left
     }
 
     #[access_control_any(roles(Role::DAO))]
     pub fn update_trusted_signer(&mut self, trusted_signer: Option<AccountId>) {
         self.trusted_signer = trusted_signer;
+        self.emit_config_updated("trusted_signer");
     }
 
     #[access_control_any(roles(Role::DAO))]
     pub fn update_trusted_blocks_submitter(&mut self, trusted_blocks_submitter: Option<AccountId>) {
         self.trusted_blocks_submitter = trusted_blocks_submitter;
+        self.emit_config_updated("trusted_blocks_submitter");
     }
 
     #[access_control_any(roles(Role::DAO))]
     pub fn update_hashes_gc_threshold(&mut self, hashes_gc_threshold: u64) {
         self.hashes_gc_threshold = hashes_gc_threshold;
+        self.emit_config_updated("hashes_gc_threshold");
     }
 
     #[access_control_any(roles(Role::DAO))]
-    pub fn attach_full_access_key(&self, public_key: PublicKey) -> Promise {
-        Promise::new(env::current_account_id()).add_full_access_key(public_key)
+    pub fn set_store_execution_roots(&mut self, enabled: bool) {
+        self.store_execution_roots = enabled;
+        self.emit_config_updated("store_execution_roots");
     }
 
     #[access_control_any(roles(Role::DAO))]
-    pub fn set_verify_bls_signatures(&mut self, enabled: bool) {
-        self.verify_bls_signatures = enabled;
+    pub fn update_max_gc_entries_per_call(&mut self, max_gc_entries_per_call: u64) {
+        self.max_gc_entries_per_call = max_gc_entries_per_call;
+        self.emit_config_updated("max_gc_entries_per_call");
     }
 
-    pub fn get_config(self) -> ContractConfig {
-        ContractConfig {
-            trusted_signer: self.trusted_signer,
-            validate_updates: self.validate_updates,
-            verify_bls_signatures: self.verify_bls_signatures,
-            hashes_gc_threshold: self.hashes_gc_threshold,
-            network: self.network,
-            trusted_blocks_submitter: self.trusted_blocks_submitter,
+    /// Sets the number of slots without finality after which the best valid update can be forced,
+    /// `None` disables the force update. The spec value is `UPDATE_TIMEOUT` slots, i.e. one
+    /// sync committee period.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_force_update_timeout(&mut self, force_update_timeout: Option<u64>) {
+        if force_update_timeout.is_none() {
+            self.best_valid_update.remove();
         }
+        self.force_update_timeout = force_update_timeout;
+        self.emit_config_updated("force_update_timeout");
     }
 
-    pub fn version(&self) -> String {
-        env!("CARGO_PKG_VERSION").to_owned()
-    }
-}
-
-impl Eth2Client {
-    fn validate_light_client_update(&self, update: &LightClientUpdate) {
-        let finalized_period =
-            compute_sync_committee_period(self.finalized_beacon_header.header.slot);
-        self.verify_finality_branch(update, finalized_period);
-
-        // Verify sync committee has sufficient participants
-        let sync_committee_bits =
-            BitVec::<u8, Lsb0>::from_slice(&update.sync_aggregate.sync_committee_bits.0);
-        let sync_committee_bits_sum: u64 = sync_committee_bits.count_ones().try_into().unwrap();
-
-        require!(
-            sync_committee_bits_sum >= MIN_SYNC_COMMITTEE_PARTICIPANTS,
-            format!(
-                "Invalid sync committee bits sum: {}",
-                sync_committee_bits_sum
-            )
-        );
-
+    /// Appends the fork to the fork schedule. The fork should be activated not before the
+    /// last scheduled fork and after the finalized beacon block.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_fork(&mut self, fork: ForkConfig) {
+        let finalized_epoch = compute_epoch_at_slot(self.finalized_beacon_header.header.slot);
         require!(
-            sync_committee_bits_sum * 3 >= (sync_committee_bits.len() * 2).try_into().unwrap(),
+            fork.epoch > finalized_epoch,
             format!(
-                "Sync committee bits sum is less than 2/3 threshold, bits sum: {}",
-                sync_committee_bits_sum
+                "The fork epoch should be higher than the finalized epoch {}",
+                finalized_epoch
             )
         );
 
-        if self.verify_bls_signatures {
-            self.verify_bls_signatures(update, sync_committee_bits, finalized_period);
+        if let Err(err) = self.network_config.add_fork(fork.clone()) {
+            env::panic_str(&err);
         }
+
+        Eth2ClientEvent::ForkAdded { fork }.emit();
     }
 
-    fn verify_finality_branch(&self, update: &LightClientUpdate, finalized_period: u64) {
-        // The active header will always be the finalized header because we don't accept updates without the finality update.
-        let active_header = &update.finalized_header.beacon;
+    #[access_control_any(roles(Role::DAO))]
+    pub fn attach_full_access_key(&self, public_key: PublicKey) -> Promise {
+        Promise::new(env::current_account_id()).add_full_access_key(public_key)
+    }
 
-        require!(
-            active_header.slot > self.finalized_beacon_header.header.slot,
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_verify_bls_signatures(&mut self, enabled: bool) {
+        self.verify_bls_signatures = enabled;
+        self.emit_config_updated("verify_bls_signatures");
+    }
+
+    /// Sets the reward accrued from the reward pool to the submitter of each accepted
+    /// light client update, `None` disables the rewards
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_light_client_update_reward(&mut self, reward: Option<NearToken>) {
+        require!(
+            reward.is_none_or(|reward| !reward.is_zero()),
+            "The reward should be positive"
+        );
+        self.light_client_update_reward = reward;
+        self.emit_config_updated("light_client_update_reward");
+    }
+
+    /// Adds the attached deposit to the reward pool
+    #[payable]
+    #[access_control_any(roles(Role::DAO))]
+    pub fn fund_reward_pool(&mut self) {
+        let amount = env::attached_deposit();
+        require!(!amount.is_zero(), "The deposit should be positive");
+        self.reward_pool_balance = self.reward_pool_balance.saturating_add(amount);
+
+        Eth2ClientEvent::RewardPoolFunded {
+            amount,
+            balance: self.reward_pool_balance,
+        }
+        .emit();
+    }
+
+    /// Transfers the `amount` of the reward pool, which is not accrued to the submitters yet,
+    /// to the caller. The amount is returned to the pool if the transfer fails.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn withdraw_reward_pool(&mut self, amount: NearToken) -> Promise {
+        require!(
+            amount <= self.reward_pool_balance,
+            "The amount exceeds the reward pool balance"
+        );
+        self.reward_pool_balance = self.reward_pool_balance.saturating_sub(amount);
+
+        Promise::new(env::predecessor_account_id())
+            .transfer(amount)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(ON_REWARD_TRANSFER_GAS)
+                    .on_reward_pool_withdrawn(amount),
+            )
+    }
+
+    #[private]
+    pub fn on_reward_pool_withdrawn(&mut self, amount: NearToken) {
+        if !env::is_promise_success() {
+            self.reward_pool_balance = self.reward_pool_balance.saturating_add(amount);
+            return;
+        }
+
+        Eth2ClientEvent::RewardPoolWithdrawn {
+            amount,
+            balance: self.reward_pool_balance,
+        }
+        .emit();
+    }
+
+    /// Transfers the unclaimed rewards to the caller. The rewards stay unclaimed if the
+    /// transfer fails.
+    pub fn claim_rewards(&mut self) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let mut stats = self.submitter_stats.get(&account_id).unwrap_or_default();
+        let amount = stats.unclaimed_rewards;
+        require!(!amount.is_zero(), "There are no unclaimed rewards");
+
+        stats.unclaimed_rewards = NearToken::from_yoctonear(0);
+        stats.claimed_rewards = stats.claimed_rewards.saturating_add(amount);
+        self.submitter_stats.insert(&account_id, &stats);
+
+        Promise::new(account_id.clone()).transfer(amount).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(ON_REWARD_TRANSFER_GAS)
+                .on_rewards_claimed(account_id, amount),
+        )
+    }
+
+    #[private]
+    pub fn on_rewards_claimed(&mut self, account_id: AccountId, amount: NearToken) {
+        if !env::is_promise_success() {
+            let mut stats = self.submitter_stats.get(&account_id).unwrap_or_default();
+            stats.unclaimed_rewards = stats.unclaimed_rewards.saturating_add(amount);
+            stats.claimed_rewards = stats.claimed_rewards.saturating_sub(amount);
+            self.submitter_stats.insert(&account_id, &stats);
+            return;
+        }
+
+        Eth2ClientEvent::RewardsClaimed { account_id, amount }.emit();
+    }
+
+    /// Returns the accepted submissions and the rewards of the account
+    pub fn get_submitter_stats(&self, account_id: AccountId) -> Option<SubmitterStats> {
+        self.submitter_stats.get(&account_id)
+    }
+
+    /// Returns the part of the reward pool which is not accrued to the submitters yet
+    pub fn get_reward_pool_balance(&self) -> NearToken {
+        self.reward_pool_balance
+    }
+
+    pub fn get_config(&self) -> ContractConfig {
+        ContractConfig {
+            trusted_signer: self.trusted_signer.clone(),
+            validate_updates: self.validate_updates,
+            verify_bls_signatures: self.verify_bls_signatures,
+            hashes_gc_threshold: self.hashes_gc_threshold,
+            network: self.network(),
+            trusted_blocks_submitter: self.trusted_blocks_submitter.clone(),
+            store_execution_roots: self.store_execution_roots,
+            max_gc_entries_per_call: self.max_gc_entries_per_call,
+            force_update_timeout: self.force_update_timeout,
+            light_client_update_reward: self.light_client_update_reward,
+        }
+    }
+
+    /// Returns the synchronization state of the client in a single call
+    pub fn get_sync_status(&self) -> SyncStatus {
+        let mut paused: Vec<String> = self
+            .pa_all_paused()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        paused.sort();
+
+        SyncStatus {
+            finalized_beacon_slot: self.finalized_beacon_header.header.slot,
+            finalized_period: compute_sync_committee_period(
+                self.finalized_beacon_header.header.slot,
+            ),
+            finalized_execution_block_number: self.last_block_number(),
+            unfinalized_head_block_number: self
+                .unfinalized_head_execution_header
+                .as_ref()
+                .map(|header| header.block_number),
+            unfinalized_tail_block_number: self.get_unfinalized_tail_block_number(),
+            client_mode: self.client_mode.clone(),
+            gc_lower_bound: self.gc_cursor,
+            oldest_provable_block_number: self.oldest_provable_block_number(),
+            next_sync_committee_present: self.next_sync_committee.is_some(),
+            paused,
+            submissions_halted: self.submissions_halted,
+            config: self.get_config(),
+        }
+    }
+
+    /// Returns the genesis parameters and fork schedule of the network
+    pub fn get_network_config(&self) -> NetworkConfig {
+        self.network_config.clone()
+    }
+
+    pub fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_owned()
+    }
+}
+
+impl Eth2Client {
+    fn verify_bootstrap(&self, bootstrap: &LightClientBootstrap) {
+        let config = &self.network_config;
+        require!(
+            config.is_valid_light_client_header(&bootstrap.header),
+            "Invalid execution block hash proof"
+        );
+
+        // Verify that the `current_sync_committee_branch` confirms `current_sync_committee`
+        // to match the one saved in the state of the bootstrap header
+        let generalized_index = config
+            .get_generalized_index_constants(bootstrap.header.beacon.slot)
+            .unwrap_or_else(|| env::panic_str("Unsupported fork"));
+        require!(
+            verify_merkle_proof(
+                H256(bootstrap.current_sync_committee.tree_hash_root().0.into()),
+                &bootstrap.current_sync_committee_branch,
+                generalized_index
+                    .current_sync_committee_tree_depth
+                    .try_into()
+                    .unwrap(),
+                generalized_index
+                    .current_sync_committee_tree_index
+                    .try_into()
+                    .unwrap(),
+                bootstrap.header.beacon.state_root
+            ),
+            "Invalid current sync committee proof"
+        );
+    }
+
+    /// Verifies the update which proves the next sync committee of the bootstrap period
+    fn verify_bootstrap_update(&self, update: &LightClientUpdate) {
+        let bootstrap_period =
+            compute_sync_committee_period(self.finalized_beacon_header.header.slot);
+        require!(
+            compute_sync_committee_period(update.finalized_header.beacon.slot) == bootstrap_period
+                && compute_sync_committee_period(update.attested_header.beacon.slot)
+                    == bootstrap_period
+                && compute_sync_committee_period(update.signature_slot) == bootstrap_period,
+            format!(
+                "The update should be finalized, attested and signed in the bootstrap period {}",
+                bootstrap_period
+            )
+        );
+
+        require!(
+            update.signature_slot > update.attested_header.beacon.slot,
+            "The signature slot should be higher than the attested header slot"
+        );
+
+        self.verify_finality_proof(update);
+        self.verify_next_sync_committee_proof(update);
+        let sync_committee_bits = Self::verify_sync_committee_participation(update);
+        self.verify_bls_signatures(update, sync_committee_bits, bootstrap_period);
+    }
+
+    fn validate_light_client_update(&self, update: &LightClientUpdate) {
+        let finalized_period =
+            compute_sync_committee_period(self.finalized_beacon_header.header.slot);
+        self.verify_finality_branch(update, finalized_period);
+        let sync_committee_bits = Self::verify_sync_committee_participation(update);
+
+        if self.verify_bls_signatures {
+            self.verify_bls_signatures(update, sync_committee_bits, finalized_period);
+        }
+    }
+
+    /// Verifies the finality proof and the BLS signature of the update by the stored sync
+    /// committees. If the BLS signatures verification is disabled, the updates are trusted
+    /// only from the trusted signer.
+    fn verify_signed_finalized_update(&self, update: &LightClientUpdate) {
+        let finalized_period =
+            compute_sync_committee_period(self.finalized_beacon_header.header.slot);
+
+        require!(
+            update.attested_header.beacon.slot >= update.finalized_header.beacon.slot,
+            "The attested header slot should be equal to or higher than the finalized header slot"
+        );
+
+        require!(
+            update.signature_slot > update.attested_header.beacon.slot,
+            "The signature slot should be higher than the attested header slot"
+        );
+
+        self.verify_finality_proof(update);
+        let sync_committee_bits = Self::verify_sync_committee_participation(update);
+
+        if self.verify_bls_signatures {
+            self.verify_bls_signatures(update, sync_committee_bits, finalized_period);
+        } else {
+            require!(
+                self.trusted_signer.as_ref() == Some(&env::predecessor_account_id()),
+                "Only the trusted signer can report the updates without BLS sigs verification"
+            );
+        }
+    }
+
+    /// Validates the non-finalized update, whose attested header is used as the finalized one
+    /// by the force update.
+    fn validate_best_valid_update(&self, update: &LightClientUpdate) {
+        let finalized_period =
+            compute_sync_committee_period(self.finalized_beacon_header.header.slot);
+        let attested_header = &update.attested_header;
+
+        require!(
+            attested_header.beacon.slot > self.finalized_beacon_header.header.slot,
+            "The attested header slot should be higher than the finalized slot"
+        );
+
+        require!(
+            update.signature_slot > attested_header.beacon.slot,
+            "The signature slot should be higher than the attested header slot"
+        );
+
+        let attested_period = compute_sync_committee_period(attested_header.beacon.slot);
+        require!(
+            attested_period == finalized_period || attested_period == finalized_period + 1,
+            format!(
+                "The acceptable update periods are '{}' and '{}' but got {}",
+                finalized_period,
+                finalized_period + 1,
+                attested_period
+            )
+        );
+
+        require!(
+            self.network_config
+                .is_valid_light_client_header(attested_header),
+            "Invalid execution block hash proof"
+        );
+
+        if attested_period != finalized_period {
+            self.verify_next_sync_committee_proof(update);
+        }
+
+        // The finalized header is forced instead of the attested one if it's newer
+        if Self::is_finality_update(update) {
+            self.verify_finality_proof(update);
+        }
+
+        let sync_committee_bits = Self::verify_min_sync_committee_participation(update);
+        if self.verify_bls_signatures {
+            self.verify_bls_signatures(update, sync_committee_bits, finalized_period);
+        }
+    }
+
+    /// Verifies that the sync committee has the minimal number of participants, the spec only
+    /// requires the minimal participation from the non-finalized updates
+    fn verify_min_sync_committee_participation(update: &LightClientUpdate) -> BitVec<u8> {
+        let sync_committee_bits =
+            BitVec::<u8, Lsb0>::from_slice(&update.sync_aggregate.sync_committee_bits.0);
+        let participation: u64 = sync_committee_bits.count_ones().try_into().unwrap();
+        require!(
+            participation >= MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            format!("Invalid sync committee bits sum: {}", participation)
+        );
+
+        sync_committee_bits
+    }
+
+    /// Whether the `new_update` is better than the `old_update`, following the `is_better_update`
+    /// of the consensus spec
+    fn is_better_update(new_update: &LightClientUpdate, old_update: &LightClientUpdate) -> bool {
+        // Compare the supermajority (> 2/3) of the sync committee participation
+        let max_active_participants = (SYNC_COMMITTEE_BITS_SIZE_IN_BYTES * 8) as u64;
+        let new_participation = Self::sync_committee_participation(new_update);
+        let old_participation = Self::sync_committee_participation(old_update);
+        let new_has_supermajority = new_participation * 3 >= max_active_participants * 2;
+        let old_has_supermajority = old_participation * 3 >= max_active_participants * 2;
+        if new_has_supermajority != old_has_supermajority {
+            return new_has_supermajority;
+        }
+        if !new_has_supermajority && new_participation != old_participation {
+            return new_participation > old_participation;
+        }
+
+        // Compare the presence of the relevant sync committee
+        let has_relevant_sync_committee = |update: &LightClientUpdate| {
+            Self::is_sync_committee_update(update)
+                && compute_sync_committee_period(update.attested_header.beacon.slot)
+                    == compute_sync_committee_period(update.signature_slot)
+        };
+        let new_has_relevant_sync_committee = has_relevant_sync_committee(new_update);
+        if new_has_relevant_sync_committee != has_relevant_sync_committee(old_update) {
+            return new_has_relevant_sync_committee;
+        }
+
+        // Compare the indication of any finality
+        let new_has_finality = Self::is_finality_update(new_update);
+        if new_has_finality != Self::is_finality_update(old_update) {
+            return new_has_finality;
+        }
+
+        // Compare the sync committee finality
+        if new_has_finality {
+            let has_sync_committee_finality = |update: &LightClientUpdate| {
+                compute_sync_committee_period(update.finalized_header.beacon.slot)
+                    == compute_sync_committee_period(update.attested_header.beacon.slot)
+            };
+            let new_has_sync_committee_finality = has_sync_committee_finality(new_update);
+            if new_has_sync_committee_finality != has_sync_committee_finality(old_update) {
+                return new_has_sync_committee_finality;
+            }
+        }
+
+        // Tiebreaker 1: the sync committee participation beyond the supermajority
+        if new_participation != old_participation {
+            return new_participation > old_participation;
+        }
+
+        // Tiebreaker 2: prefer the older data (fewer changes to the best update)
+        if new_update.attested_header.beacon.slot != old_update.attested_header.beacon.slot {
+            return new_update.attested_header.beacon.slot < old_update.attested_header.beacon.slot;
+        }
+        new_update.signature_slot < old_update.signature_slot
+    }
+
+    fn is_sync_committee_update(update: &LightClientUpdate) -> bool {
+        update
+            .next_sync_committee_branch
+            .as_ref()
+            .is_some_and(|branch| branch.iter().any(|node| !node.0.is_zero()))
+    }
+
+    fn is_finality_update(update: &LightClientUpdate) -> bool {
+        update.finality_branch.iter().any(|node| !node.0.is_zero())
+    }
+
+    fn sync_committee_participation(update: &LightClientUpdate) -> u64 {
+        BitVec::<u8, Lsb0>::from_slice(&update.sync_aggregate.sync_committee_bits.0)
+            .count_ones()
+            .try_into()
+            .unwrap()
+    }
+
+    /// Verifies that the sync committee has sufficient participants
+    fn verify_sync_committee_participation(update: &LightClientUpdate) -> BitVec<u8> {
+        let sync_committee_bits =
+            BitVec::<u8, Lsb0>::from_slice(&update.sync_aggregate.sync_committee_bits.0);
+        let sync_committee_bits_sum: u64 = sync_committee_bits.count_ones().try_into().unwrap();
+
+        require!(
+            sync_committee_bits_sum >= MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            format!(
+                "Invalid sync committee bits sum: {}",
+                sync_committee_bits_sum
+            )
+        );
+
+        require!(
+            sync_committee_bits_sum * 3 >= (sync_committee_bits.len() * 2).try_into().unwrap(),
+            format!(
+                "Sync committee bits sum is less than 2/3 threshold, bits sum: {}",
+                sync_committee_bits_sum
+            )
+        );
+
+        sync_committee_bits
+    }
+
+    fn verify_finality_branch(&self, update: &LightClientUpdate, finalized_period: u64) {
+        // The active header will always be the finalized header because we don't accept updates without the finality update.
+        let active_header = &update.finalized_header.beacon;
+
+        require!(
+            active_header.slot > self.finalized_beacon_header.header.slot,
             "The active header slot number should be higher than the finalized slot"
         );
 
@@ -460,12 +1336,23 @@ impl Eth2Client {
             )
         );
 
-        let config = NetworkConfig::new(&self.network);
+        self.verify_finality_proof(update);
+
+        // Verify that the `next_sync_committee`, if present, actually is the next sync committee saved in the
+        // state of the `active_header`
+        if update_period != finalized_period {
+            self.verify_next_sync_committee_proof(update);
+        }
+    }
+
+    fn verify_finality_proof(&self, update: &LightClientUpdate) {
+        let config = &self.network_config;
 
         // Verify that the `finality_branch`, confirms `finalized_header`
         // to match the finalized checkpoint root saved in the state of `attested_header`.
-        let generalized_index =
-            config.get_generalized_index_constants(update.finalized_header.beacon.slot);
+        let generalized_index = config
+            .get_generalized_index_constants(update.finalized_header.beacon.slot)
+            .unwrap_or_else(|| env::panic_str("Unsupported fork"));
         require!(
             verify_merkle_proof(
                 H256(update.finalized_header.beacon.tree_hash_root().0.into()),
@@ -481,35 +1368,35 @@ impl Eth2Client {
             config.is_valid_light_client_header(&update.finalized_header),
             "Invalid execution block hash proof"
         );
+    }
 
-        // Verify that the `next_sync_committee`, if present, actually is the next sync committee saved in the
-        // state of the `active_header`
-        if update_period != finalized_period {
-            let generalized_index =
-                config.get_generalized_index_constants(update.attested_header.beacon.slot);
+    fn verify_next_sync_committee_proof(&self, update: &LightClientUpdate) {
+        let generalized_index = self
+            .network_config
+            .get_generalized_index_constants(update.attested_header.beacon.slot)
+            .unwrap_or_else(|| env::panic_str("Unsupported fork"));
 
-            let sync_committee_update = update
-                .next_sync_committee
-                .as_ref()
-                .unwrap_or_else(|| env::panic_str("The sync committee update is missed"));
+        let sync_committee_update = update
+            .next_sync_committee
+            .as_ref()
+            .unwrap_or_else(|| env::panic_str("The sync committee update is missed"));
 
-            require!(
-                verify_merkle_proof(
-                    H256(sync_committee_update.tree_hash_root().0.into()),
-                    &update.next_sync_committee_branch.clone().unwrap(),
-                    generalized_index
-                        .sync_committee_tree_depth
-                        .try_into()
-                        .unwrap(),
-                    generalized_index
-                        .sync_committee_tree_index
-                        .try_into()
-                        .unwrap(),
-                    update.attested_header.beacon.state_root
-                ),
-                "Invalid next sync committee proof"
-            );
-        }
+        require!(
+            verify_merkle_proof(
+                H256(sync_committee_update.tree_hash_root().0.into()),
+                &update.next_sync_committee_branch.clone().unwrap(),
+                generalized_index
+                    .sync_committee_tree_depth
+                    .try_into()
+                    .unwrap(),
+                generalized_index
+                    .sync_committee_tree_index
+                    .try_into()
+                    .unwrap(),
+                update.attested_header.beacon.state_root
+            ),
+            "Invalid next sync committee proof"
+        );
     }
 
     fn verify_bls_signatures(
@@ -518,7 +1405,7 @@ impl Eth2Client {
         sync_committee_bits: BitVec<u8>,
         finalized_period: u64,
     ) {
-        let config = NetworkConfig::new(&self.network);
+        let config = &self.network_config;
         let signature_period = compute_sync_committee_period(update.signature_slot);
 
         // Verify signature period does not skip a sync committee period
@@ -533,14 +1420,12 @@ impl Eth2Client {
         );
 
         // Verify sync committee aggregate signature
-        let sync_committee = if signature_period == finalized_period {
-            self.current_sync_committee.get().unwrap()
+        let sync_committee_keys = if signature_period == finalized_period {
+            self.current_sync_committee_keys.get().unwrap()
         } else {
-            self.next_sync_committee.get().unwrap()
+            self.next_sync_committee_keys.get().unwrap()
         };
 
-        let participant_pubkeys =
-            get_participant_pubkeys(&sync_committee.pubkeys.0, &sync_committee_bits);
         let fork_version = config
             .compute_fork_version_by_slot(update.signature_slot)
             .unwrap_or_else(|| env::panic_str("Unsupported fork"));
@@ -576,18 +1461,8 @@ impl Eth2Client {
 
         let msg_g2 = env::bls12381_p2_sum(&msg_g2_concat);
 
-        let pubkeys_bytes: Vec<u8> = participant_pubkeys
-            .iter()
-            .flat_map(|x| x.0.iter())
-            .copied()
-            .collect();
-        let pks_decompress = env::bls12381_p1_decompress(&pubkeys_bytes);
-        let mut pks_with_sign = Vec::new();
-        for chunk in pks_decompress.chunks(96) {
-            pks_with_sign.push(0u8);
-            pks_with_sign.extend_from_slice(chunk);
-        }
-        let pk_agg = env::bls12381_p1_sum(&pks_with_sign);
+        let pk_agg =
+            Self::aggregate_participant_pubkeys(&sync_committee_keys, &sync_committee_bits);
 
         let mut gen = ECP::generator();
         gen.neg();
@@ -599,7 +1474,53 @@ impl Eth2Client {
         require!(ok, "Failed to verify the bls signature");
     }
 
-    fn commit_light_client_update(&mut self, update: LightClientUpdate) {
+    fn decompress_sync_committee(sync_committee: &SyncCommittee) -> DecompressedSyncCommittee {
+        let pubkeys_bytes: Vec<u8> = sync_committee
+            .pubkeys
+            .0
+            .iter()
+            .flat_map(|x| x.0.iter())
+            .copied()
+            .collect();
+
+        DecompressedSyncCommittee {
+            pubkeys: env::bls12381_p1_decompress(&pubkeys_bytes),
+            aggregate_pubkey: env::bls12381_p1_decompress(&sync_committee.aggregate_pubkey.0),
+        }
+    }
+
+    /// Sums the public keys of the participants. If most of the committee participates,
+    /// the keys of the non-participants are subtracted from the aggregate key instead.
+    fn aggregate_participant_pubkeys(
+        sync_committee_keys: &DecompressedSyncCommittee,
+        sync_committee_bits: &BitVec<u8>,
+    ) -> Vec<u8> {
+        let subtract_non_participants =
+            sync_committee_bits.count_ones() * 2 > sync_committee_bits.len();
+
+        // Each point is prefixed with the sign byte, 1 means that the point is subtracted
+        let mut pks_with_sign = Vec::new();
+        if subtract_non_participants {
+            pks_with_sign.push(0u8);
+            pks_with_sign.extend_from_slice(&sync_committee_keys.aggregate_pubkey);
+        }
+
+        for (pubkey, is_participant) in sync_committee_keys
+            .pubkeys
+            .chunks(96)
+            .zip(sync_committee_bits.iter().by_vals())
+        {
+            if is_participant != subtract_non_participants {
+                pks_with_sign.push(u8::from(subtract_non_participants));
+                pks_with_sign.extend_from_slice(pubkey);
+            }
+        }
+
+        env::bls12381_p1_sum(&pks_with_sign)
+    }
+
+    fn commit_light_client_update(&mut self, update: LightClientUpdate, forced: bool) {
+        let participation = Self::sync_committee_participation(&update);
         // Update finalized header
         let finalized_header_update = update.finalized_header;
         let finalized_period =
@@ -607,12 +1528,30 @@ impl Eth2Client {
         let update_period = compute_sync_committee_period(finalized_header_update.beacon.slot);
 
         if update_period == finalized_period + 1 {
+            let next_sync_committee = update.next_sync_committee.unwrap();
             self.current_sync_committee
                 .set(&self.next_sync_committee.get().unwrap());
-            self.next_sync_committee
-                .set(&update.next_sync_committee.unwrap());
+            self.current_sync_committee_keys
+                .set(&self.next_sync_committee_keys.get().unwrap());
+            self.next_sync_committee_keys
+                .set(&Self::decompress_sync_committee(&next_sync_committee));
+            self.next_sync_committee.set(&next_sync_committee);
+
+            Eth2ClientEvent::SyncCommitteeRotated {
+                period: update_period,
+            }
+            .emit();
         }
 
+        Eth2ClientEvent::LightClientUpdateAccepted {
+            old_finalized_slot: self.finalized_beacon_header.header.slot,
+            new_finalized_slot: finalized_header_update.beacon.slot,
+            period: update_period,
+            participation,
+            forced,
+        }
+        .emit();
+
         #[cfg(feature = "logs")]
         env::log_str(
             format!(
@@ -622,32 +1561,311 @@ impl Eth2Client {
             .as_str(),
         );
 
+        // The forced header is not finalized, so its state can't be used for the historical proofs
+        if !forced {
+            self.store_finalized_beacon_state_root(
+                finalized_header_update.beacon.slot,
+                finalized_header_update.beacon.state_root,
+            );
+        }
         self.finalized_beacon_header = finalized_header_update.into();
+        self.best_valid_update.remove();
         self.client_mode = ClientMode::SubmitHeader;
     }
 
-    /// Remove information about the headers that are at least as old as the given block number.
-    /// This method could go out of gas if the client was not synced for a while, to fix that
-    /// you need to increase the `hashes_gc_threshold` by calling `update_hashes_gc_threshold()`
-    fn gc_finalized_execution_blocks(&mut self, mut header_number: u64) {
-        loop {
-            if self
-                .finalized_execution_blocks
-                .remove(&header_number)
-                .is_some()
+    /// Stores the state root of the finalized beacon block and removes the oldest one beyond
+    /// `MAX_FINALIZED_BEACON_STATE_ROOTS`
+    fn store_finalized_beacon_state_root(&mut self, slot: u64, state_root: H256) {
+        let count = self.finalized_beacon_state_roots_count;
+        self.finalized_beacon_state_roots.insert(&slot, &state_root);
+        self.finalized_beacon_state_slots.insert(&count, &slot);
+
+        if count >= MAX_FINALIZED_BEACON_STATE_ROOTS {
+            if let Some(oldest_slot) = self
+                .finalized_beacon_state_slots
+                .remove(&(count - MAX_FINALIZED_BEACON_STATE_ROOTS))
             {
-                if header_number == 0 {
-                    break;
-                } else {
-                    header_number -= 1;
+                self.finalized_beacon_state_roots.remove(&oldest_slot);
+            }
+        }
+        self.finalized_beacon_state_roots_count = count + 1;
+    }
+
+    fn is_execution_header_submission_allowed(&self) {
+        self.require_submissions_not_halted();
+        if let Some(trusted_blocks_submitter) = &self.trusted_blocks_submitter {
+            require!(
+                &env::predecessor_account_id() == trusted_blocks_submitter,
+                "Eth-client is deployed as trust mode, only trusted_blocks_submitter can submit blocks"
+            );
+        }
+
+        require!(
+            self.client_mode == ClientMode::SubmitHeader,
+            "Client is not in SubmitHeader mode"
+        );
+    }
+
+    /// Verifies that the given headers extend the unfinalized chain down to the finalized
+    /// execution block and stores their hashes. The gc is applied once per call, right before
+    /// the last header is processed, and removes at least as many blocks as were submitted, so
+    /// the gc cursor doesn't fall behind the gc target.
+    fn process_execution_headers(&mut self, block_headers: &[BlockHeader]) {
+        let finalized_execution_header = self.finalized_execution_header.get().unwrap();
+        let last_header_index = block_headers.len() - 1;
+
+        for (header_index, block_header) in block_headers.iter().enumerate() {
+            let block_hash = block_header.calculate_hash();
+            let expected_block_hash = self
+                .unfinalized_tail_execution_header
+                .as_ref()
+                .map(|header| header.parent_hash)
+                .unwrap_or(self.finalized_beacon_header.execution_block_hash);
+            require!(
+                block_hash == expected_block_hash,
+                format!(
+                    "The expected block hash is {:#?} but got {:#?}.",
+                    expected_block_hash, block_hash
+                )
+            );
+
+            let insert_result = self
+                .finalized_execution_blocks
+                .insert(&block_header.number, &block_hash);
+
+            require!(
+                insert_result.is_none(),
+                format!("The block {:#?} already submitted!", &block_hash)
+            );
+
+            self.finalized_execution_timestamps
+                .insert(&block_header.number, &block_header.timestamp);
+            if self.store_execution_roots {
+                self.finalized_execution_roots
+                    .insert(&block_header.number, &block_header.into());
+            }
+
+            if header_index == last_header_index {
+                self.apply_gc(&finalized_execution_header, block_headers.len() as u64);
+            }
+
+            if block_header.number == finalized_execution_header.block_number + 1 {
+                require!(
+                    header_index == last_header_index,
+                    "The chain is closed before the end of the headers batch"
+                );
+
+                let finalized_execution_header_hash = self
+                    .finalized_execution_blocks
+                    .get(&finalized_execution_header.block_number)
+                    .unwrap();
+                require!(
+                    block_header.parent_hash == finalized_execution_header_hash,
+                    "The chain cannot be closed"
+                );
+
+                #[cfg(feature = "logs")]
+                env::log_str(
+                    format!(
+                        "Current finalized block number: {}, New finalized block number: {}",
+                        finalized_execution_header.block_number,
+                        self.unfinalized_head_execution_header
+                            .as_ref()
+                            .unwrap()
+                            .block_number
+                    )
+                    .as_str(),
+                );
+
+                Eth2ClientEvent::HeadersBatchFinalized {
+                    start_block: finalized_execution_header.block_number + 1,
+                    end_block: self
+                        .unfinalized_head_execution_header
+                        .as_ref()
+                        .unwrap()
+                        .block_number,
+                    submitter: env::predecessor_account_id(),
                 }
+                .emit();
+
+                self.finalized_execution_header
+                    .set(self.unfinalized_head_execution_header.as_ref().unwrap());
+                self.unfinalized_tail_execution_header = None;
+                self.unfinalized_head_execution_header = None;
+                self.client_mode = ClientMode::SubmitLightClientUpdate;
             } else {
-                break;
+                let block_info = ExecutionHeaderInfo {
+                    parent_hash: block_header.parent_hash,
+                    block_number: block_header.number,
+                    submitter: env::predecessor_account_id(),
+                };
+
+                if self.unfinalized_head_execution_header.is_none() {
+                    self.unfinalized_head_execution_header = Some(block_info.clone());
+                }
+                self.unfinalized_tail_execution_header = Some(block_info);
             }
+
+            #[cfg(feature = "logs")]
+            env::log_str(
+                format!(
+                    "Submitted header number {}, hash {:#?}",
+                    block_header.number, block_hash
+                )
+                .as_str(),
+            );
         }
     }
 
+    fn apply_gc(&mut self, finalized_execution_header: &ExecutionHeaderInfo, headers_count: u64) {
+        if let Some(diff_between_unfinalized_head_and_tail) =
+            self.get_diff_between_unfinalized_head_and_tail()
+        {
+            let header_number_to_remove = (finalized_execution_header.block_number
+                + diff_between_unfinalized_head_and_tail)
+                .saturating_sub(self.hashes_gc_threshold);
+
+            require!(
+                header_number_to_remove < finalized_execution_header.block_number,
+                "The `hashes_gc_threshold` is not enough to be able to apply gc correctly"
+            );
+
+            if header_number_to_remove > 0 {
+                self.gc_target = self.gc_target.max(header_number_to_remove + 1);
+            }
+        }
+
+        self.gc_finalized_execution_blocks(
+            headers_count.saturating_add(self.max_gc_entries_per_call),
+        );
+    }
+
+    /// Remove information about at most `max_entries` headers below the gc target, starting
+    /// from the gc cursor. The progress is persisted, so a large gc backlog left after a long
+    /// outage is processed across several calls instead of running out of gas in one.
+    /// Returns the number of processed block numbers.
+    fn gc_finalized_execution_blocks(&mut self, max_entries: u64) -> u64 {
+        let gc_end = self
+            .gc_target
+            .min(self.gc_cursor.saturating_add(max_entries));
+
+        if gc_end <= self.gc_cursor {
+            return 0;
+        }
+
+        for block_number in self.gc_cursor..gc_end {
+            self.finalized_execution_blocks.remove(&block_number);
+            self.finalized_execution_roots.remove(&block_number);
+            self.finalized_execution_timestamps.remove(&block_number);
+        }
+
+        let processed_entries = gc_end - self.gc_cursor;
+        Eth2ClientEvent::GcExecuted {
+            start_block: self.gc_cursor,
+            end_block: gc_end,
+            gc_target: self.gc_target,
+        }
+        .emit();
+        self.gc_cursor = gc_end;
+
+        #[cfg(feature = "logs")]
+        env::log_str(
+            format!(
+                "Garbage collected {} blocks, gc cursor: {}, gc target: {}",
+                processed_entries, self.gc_cursor, self.gc_target
+            )
+            .as_str(),
+        );
+
+        processed_entries
+    }
+
+    /// Removes at most `max_entries` unfinalized headers left by the v0.1.0 layout.
+    /// Returns the number of the removed headers
+    fn gc_unfinalized_headers_v1(&mut self, max_entries: u64) -> u64 {
+        let Some(unfinalized_headers) = self.unfinalized_headers_v1.as_mut() else {
+            return 0;
+        };
+
+        let mut removed_headers = 0;
+        while removed_headers < max_entries && !unfinalized_headers.is_empty() {
+            // The last key is removed, so the keys and values vectors are just popped
+            let hash = unfinalized_headers
+                .keys_as_vector()
+                .get(unfinalized_headers.len() - 1)
+                .unwrap();
+            unfinalized_headers.remove(&hash);
+            removed_headers += 1;
+        }
+
+        if unfinalized_headers.is_empty() {
+            self.unfinalized_headers_v1 = None;
+        }
+        removed_headers
+    }
+
+    /// Counts the accepted submissions of the caller along with the burnt gas and accrues
+    /// the light client update rewards while the reward pool has enough balance.
+    /// The submissions of the accounts beyond `MAX_TRACKED_SUBMITTERS` aren't recorded.
+    fn record_submission(&mut self, light_client_updates: u64, execution_headers: u64) {
+        let submitter = env::predecessor_account_id();
+        let mut stats = match self.submitter_stats.get(&submitter) {
+            Some(stats) => stats,
+            None if self.tracked_submitters < MAX_TRACKED_SUBMITTERS => {
+                self.tracked_submitters += 1;
+                SubmitterStats::default()
+            }
+            None => return,
+        };
+        stats.light_client_updates += light_client_updates;
+        stats.execution_headers += execution_headers;
+        stats.gas_burnt = stats.gas_burnt.saturating_add(env::used_gas());
+
+        if let Some(reward) = self.light_client_update_reward {
+            let rewarded_updates = (light_client_updates as u128)
+                .min(self.reward_pool_balance.as_yoctonear() / reward.as_yoctonear());
+            let rewards = reward.saturating_mul(rewarded_updates);
+            self.reward_pool_balance = self.reward_pool_balance.saturating_sub(rewards);
+            stats.unclaimed_rewards = stats.unclaimed_rewards.saturating_add(rewards);
+        }
+
+        self.submitter_stats.insert(&submitter, &stats);
+    }
+
+    /// Returns the known network with the stored genesis validators root or the custom one
+    fn network(&self) -> Network {
+        [Network::Mainnet, Network::Goerli, Network::Sepolia]
+            .into_iter()
+            .find(|network| {
+                NetworkConfig::new(network).genesis_validators_root
+                    == self.network_config.genesis_validators_root
+            })
+            .unwrap_or_else(|| Network::Custom(self.network_config.clone()))
+    }
+
+    fn emit_config_updated(&self, parameter: &str) {
+        Eth2ClientEvent::ConfigUpdated {
+            parameter: parameter.to_owned(),
+            config: self.get_config(),
+        }
+        .emit();
+    }
+
+    /// Returns the current slot of the network by the block time
+    fn current_slot(&self) -> u64 {
+        self.network_config
+            .compute_slot_at_timestamp(env::block_timestamp() / 1_000_000_000)
+    }
+
+    fn require_submissions_not_halted(&self) {
+        require!(
+            !self.submissions_halted,
+            "The submissions are halted by the reported conflicting updates"
+        );
+    }
+
     fn is_light_client_update_allowed(&self) {
+        self.require_submissions_not_halted();
         require!(
             self.client_mode == ClientMode::SubmitLightClientUpdate,
             "Client is not in SubmitLightClientUpdate mode"
@@ -661,6 +1879,14 @@ impl Eth2Client {
         }
     }
 
+    /// Returns the lowest finalized execution block number within the `hashes_gc_threshold`
+    /// window. The blocks below it aren't provable even if they aren't removed yet, since the gc
+    /// only runs on the header submission and `gc_step`.
+    fn oldest_provable_block_number(&self) -> u64 {
+        let window_start = (self.last_block_number() + 1).saturating_sub(self.hashes_gc_threshold);
+        self.gc_target.max(self.gc_cursor).max(window_start)
+    }
+
     fn get_diff_between_unfinalized_head_and_tail(&self) -> Option<u64> {
         let head_block_number = self
             .unfinalized_head_execution_header
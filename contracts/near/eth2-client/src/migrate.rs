@@ -1,32 +1,131 @@
 use crate::*;
 use near_sdk::collections::UnorderedMap;
 
+/// Version of the state layout of the current contract
+pub const STATE_VERSION: u32 = 3;
+
+const STATE_KEY: &[u8] = b"STATE";
+
+/// Key of the version tag of the stored state, the layouts before v3 aren't tagged
+pub(crate) const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &borsh::to_vec(&STATE_VERSION).unwrap());
+}
+
+fn read_state_version() -> Option<u32> {
+    env::storage_read(STATE_VERSION_KEY).map(|version| u32::try_from_slice(&version).unwrap())
+}
+
+/// Layout of v0.1.0, the execution headers were submitted before the light client updates
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Eth2ClientV1 {
-    trusted_signer: Option<AccountId>,
+    pub trusted_signer: Option<AccountId>,
     #[deprecated]
-    paused: u128,
-    validate_updates: bool,
-    verify_bls_signatures: bool,
-    hashes_gc_threshold: u64,
-    network: Network,
-    finalized_execution_blocks: LookupMap<u64, H256>,
-    unfinalized_headers: UnorderedMap<H256, ExecutionHeaderInfo>,
-    submitters: LookupMap<AccountId, u32>,
-    max_submitted_blocks_by_account: u32,
-    min_storage_balance_for_submitter: u128,
-    finalized_beacon_header: ExtendedBeaconBlockHeader,
-    finalized_execution_header: LazyOption<ExecutionHeaderInfo>,
-    current_sync_committee: LazyOption<SyncCommittee>,
-    next_sync_committee: LazyOption<SyncCommittee>,
+    pub paused: u128,
+    pub validate_updates: bool,
+    pub verify_bls_signatures: bool,
+    pub hashes_gc_threshold: u64,
+    pub network: Network,
+    pub finalized_execution_blocks: LookupMap<u64, H256>,
+    pub unfinalized_headers: UnorderedMap<H256, ExecutionHeaderInfo>,
+    pub submitters: LookupMap<AccountId, u32>,
+    pub max_submitted_blocks_by_account: u32,
+    pub min_storage_balance_for_submitter: u128,
+    pub finalized_beacon_header: ExtendedBeaconBlockHeader,
+    pub finalized_execution_header: LazyOption<ExecutionHeaderInfo>,
+    pub current_sync_committee: LazyOption<SyncCommittee>,
+    pub next_sync_committee: LazyOption<SyncCommittee>,
 }
 
-#[near]
-impl Eth2Client {
-    #[private]
-    #[init(ignore_state)]
-    pub fn migrate() -> Self {
-        let old_state: Eth2ClientV1 = env::state_read().expect("failed");
+/// Layout with the client modes, the execution headers are submitted after the light client updates
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct Eth2ClientV2 {
+    pub trusted_signer: Option<AccountId>,
+    #[deprecated]
+    pub paused: u128,
+    pub validate_updates: bool,
+    pub verify_bls_signatures: bool,
+    pub hashes_gc_threshold: u64,
+    pub network: Network,
+    pub finalized_execution_blocks: LookupMap<u64, H256>,
+    pub finalized_beacon_header: ExtendedBeaconBlockHeader,
+    pub finalized_execution_header: LazyOption<ExecutionHeaderInfo>,
+    pub current_sync_committee: LazyOption<SyncCommittee>,
+    pub next_sync_committee: LazyOption<SyncCommittee>,
+    pub client_mode: ClientMode,
+    pub unfinalized_head_execution_header: Option<ExecutionHeaderInfo>,
+    pub unfinalized_tail_execution_header: Option<ExecutionHeaderInfo>,
+    pub trusted_blocks_submitter: Option<AccountId>,
+}
+
+/// The state layouts the contract can be migrated from.
+/// A new layout is added as the next variant along with a single `From` step
+/// from the previous one, so any older state is upgraded step by step.
+pub enum VersionedEth2Client {
+    V1(Eth2ClientV1),
+    V2(Eth2ClientV2),
+    V3(Eth2Client),
+}
+
+impl VersionedEth2Client {
+    /// Reads the stored state with the layout of the given version.
+    /// The borsh deserialization fails on a layout that doesn't consume exactly all the bytes.
+    pub fn read(version: u32) -> Self {
+        let state = env::storage_read(STATE_KEY)
+            .unwrap_or_else(|| env::panic_str("The contract is not initialized"));
+
+        let result = match version {
+            1 => Eth2ClientV1::try_from_slice(&state).map(Self::V1),
+            2 => Eth2ClientV2::try_from_slice(&state).map(Self::V2),
+            3 => Eth2Client::try_from_slice(&state).map(Self::V3),
+            _ => env::panic_str(&format!("Unsupported state version {}", version)),
+        };
+
+        result.unwrap_or_else(|_| {
+            env::panic_str(&format!(
+                "The stored state doesn't match the layout of version {}",
+                version
+            ))
+        })
+    }
+
+    pub fn into_latest(self) -> Eth2Client {
+        match self {
+            Self::V1(mut state) => {
+                // The map can be too large to be cleared within the migration, so it's moved to
+                // the latest layout and its entries are removed in batches by the `gc_step`
+                let unfinalized_headers = std::mem::replace(
+                    &mut state.unfinalized_headers,
+                    UnorderedMap::new(StorageKey::__DeprecatedUnfinalizedHeaders),
+                );
+                let mut contract = Self::V2(state.into()).into_latest();
+                if !unfinalized_headers.is_empty() {
+                    contract.unfinalized_headers_v1 = Some(unfinalized_headers);
+                }
+                contract
+            }
+            Self::V2(state) => Self::V3(state.into()).into_latest(),
+            Self::V3(state) => state,
+        }
+    }
+}
+
+impl From<Eth2ClientV1> for Eth2ClientV2 {
+    fn from(old_state: Eth2ClientV1) -> Self {
+        // The unfinalized headers of v0.1.0 are dropped, so the headers are submitted
+        // again if the finalized beacon header is ahead of the finalized execution header
+        let finalized_execution_header = old_state.finalized_execution_header.get().unwrap();
+        let is_finalized_execution_header_synced = old_state
+            .finalized_execution_blocks
+            .get(&finalized_execution_header.block_number)
+            == Some(old_state.finalized_beacon_header.execution_block_hash);
+        let client_mode = if is_finalized_execution_header_synced {
+            ClientMode::SubmitLightClientUpdate
+        } else {
+            ClientMode::SubmitHeader
+        };
+
         #[allow(deprecated)]
         Self {
             trusted_signer: old_state.trusted_signer,
@@ -40,10 +139,129 @@ impl Eth2Client {
             finalized_execution_header: old_state.finalized_execution_header,
             current_sync_committee: old_state.current_sync_committee,
             next_sync_committee: old_state.next_sync_committee,
-            client_mode: ClientMode::SubmitLightClientUpdate,
+            client_mode,
             unfinalized_head_execution_header: None,
             unfinalized_tail_execution_header: None,
             trusted_blocks_submitter: None,
         }
     }
 }
+
+impl From<Eth2ClientV2> for Eth2Client {
+    fn from(old_state: Eth2ClientV2) -> Self {
+        // The previous unbounded gc has already removed the blocks outside of the
+        // `hashes_gc_threshold` window of the finalized block
+        let gc_cursor = old_state
+            .finalized_execution_header
+            .get()
+            .unwrap()
+            .block_number
+            .saturating_sub(old_state.hashes_gc_threshold + 1);
+        let network_config = NetworkConfig::new(&old_state.network);
+        // Both sync committees are set on the initialization and on each rotation by all the
+        // older layouts, so their keys are always cached for the BLS signatures verification
+        let current_sync_committee_keys =
+            Self::decompress_sync_committee(&old_state.current_sync_committee.get().unwrap());
+        let next_sync_committee_keys = Self::decompress_sync_committee(
+            &old_state
+                .next_sync_committee
+                .get()
+                .unwrap_or_else(|| env::panic_str("The next sync committee is missing")),
+        );
+        #[allow(deprecated)]
+        let mut contract = Self {
+            trusted_signer: old_state.trusted_signer,
+            paused: old_state.paused,
+            validate_updates: old_state.validate_updates,
+            verify_bls_signatures: old_state.verify_bls_signatures,
+            hashes_gc_threshold: old_state.hashes_gc_threshold,
+            finalized_execution_blocks: old_state.finalized_execution_blocks,
+            finalized_beacon_header: old_state.finalized_beacon_header,
+            finalized_execution_header: old_state.finalized_execution_header,
+            current_sync_committee: old_state.current_sync_committee,
+            next_sync_committee: old_state.next_sync_committee,
+            client_mode: old_state.client_mode,
+            unfinalized_head_execution_header: old_state.unfinalized_head_execution_header,
+            unfinalized_tail_execution_header: old_state.unfinalized_tail_execution_header,
+            trusted_blocks_submitter: old_state.trusted_blocks_submitter,
+            store_execution_roots: false,
+            finalized_execution_roots: LookupMap::new(StorageKey::FinalizedExecutionRoots),
+            gc_cursor,
+            gc_target: gc_cursor,
+            max_gc_entries_per_call: DEFAULT_MAX_GC_ENTRIES_PER_CALL,
+            network_config,
+            force_update_timeout: None,
+            best_valid_update: LazyOption::new(StorageKey::BestValidUpdate, None),
+            conflicting_updates_evidence: LazyOption::new(
+                StorageKey::ConflictingUpdatesEvidence,
+                None,
+            ),
+            submissions_halted: false,
+            unfinalized_headers_v1: None,
+            finalized_beacon_state_roots: LookupMap::new(StorageKey::FinalizedBeaconStateRoots),
+            finalized_beacon_state_slots: LookupMap::new(StorageKey::FinalizedBeaconStateSlots),
+            finalized_beacon_state_roots_count: 0,
+            finalized_execution_timestamps: LookupMap::new(
+                StorageKey::FinalizedExecutionTimestamps,
+            ),
+            current_sync_committee_keys: LazyOption::new(
+                StorageKey::CurrentSyncCommitteeKeys,
+                Some(&current_sync_committee_keys),
+            ),
+            next_sync_committee_keys: LazyOption::new(
+                StorageKey::NextSyncCommitteeKeys,
+                Some(&next_sync_committee_keys),
+            ),
+            submitter_stats: LookupMap::new(StorageKey::SubmitterStats),
+            tracked_submitters: 0,
+            light_client_update_reward: None,
+            reward_pool_balance: NearToken::from_yoctonear(0),
+        };
+
+        contract.store_finalized_beacon_state_root(
+            contract.finalized_beacon_header.header.slot,
+            contract.finalized_beacon_header.header.state_root,
+        );
+        contract
+    }
+}
+
+#[near]
+impl Eth2Client {
+    /// Upgrades the state from the layout of `from_version` to the latest one.
+    /// The version of the untagged layouts before v3 has to be known by the caller,
+    /// the call on the latest layout leaves the state untouched.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(from_version: u32) -> Self {
+        if let Some(stored_version) = read_state_version() {
+            require!(
+                stored_version == from_version,
+                format!("The stored state version is {}", stored_version)
+            );
+        }
+
+        let mut contract = VersionedEth2Client::read(from_version).into_latest();
+        if from_version == STATE_VERSION {
+            return contract;
+        }
+
+        // The layout of v0.1.0 has no access control, the contract becomes the super admin
+        if from_version == 1 {
+            contract.acl_init_super_admin(env::predecessor_account_id());
+        }
+        write_state_version();
+
+        Eth2ClientEvent::StateMigrated {
+            from_version,
+            to_version: STATE_VERSION,
+        }
+        .emit();
+        contract
+    }
+
+    /// Returns the version of the stored state layout, `None` for the untagged layouts before v3
+    pub fn state_version() -> Option<u32> {
+        read_state_version()
+    }
+}
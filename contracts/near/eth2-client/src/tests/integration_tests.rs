@@ -1,10 +1,11 @@
 #[cfg(test)]
 mod integration_tests {
+    use crate::migrate::STATE_VERSION;
     use crate::tests::utils::get_sepolia_test_data;
     use crate::tests::utils::InitOptions;
     use borsh::{BorshDeserialize, BorshSerialize};
     use eth2_utility::consensus::Network;
-    use eth2_utility::types::InitInput;
+    use eth2_utility::types::{ClientMode, InitInput};
     use eth_types::eth2::{ExtendedBeaconBlockHeader, SyncCommittee};
     use eth_types::{Address, Bloom, H256, H64, U256};
     use near_sdk::{Gas, NearToken};
@@ -13,6 +14,8 @@ mod integration_tests {
     use serde::{Deserialize, Serialize};
 
     const WASM_FILEPATH: &str = "../target/near/eth2_client/eth2_client.wasm";
+    const V0_1_0_WASM_FILEPATH: &str = "src/data/eth2_client_v0.1.0_testnet.wasm";
+    const BASELINE_WASM_FILEPATH: &str = "src/data/eth2_client_v0.7.1.wasm";
 
     #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
     struct BlockHeaderV1 {
@@ -79,6 +82,36 @@ mod integration_tests {
         }
     }
 
+    /// Init input of the currently deployed client with the v2 state layout
+    #[derive(Clone, BorshDeserialize, BorshSerialize)]
+    struct InitInputV2 {
+        pub network: Network,
+        pub finalized_execution_header: eth_types::BlockHeader,
+        pub finalized_beacon_header: ExtendedBeaconBlockHeader,
+        pub current_sync_committee: SyncCommittee,
+        pub next_sync_committee: SyncCommittee,
+        pub validate_updates: bool,
+        pub verify_bls_signatures: bool,
+        pub hashes_gc_threshold: u64,
+        pub trusted_signer: Option<near_sdk::AccountId>,
+    }
+
+    impl From<InitInput> for InitInputV2 {
+        fn from(message: InitInput) -> Self {
+            Self {
+                network: message.network,
+                finalized_execution_header: message.finalized_execution_header,
+                finalized_beacon_header: message.finalized_beacon_header,
+                current_sync_committee: message.current_sync_committee,
+                next_sync_committee: message.next_sync_committee,
+                validate_updates: message.validate_updates,
+                verify_bls_signatures: message.verify_bls_signatures,
+                hashes_gc_threshold: message.hashes_gc_threshold,
+                trusted_signer: message.trusted_signer,
+            }
+        }
+    }
+
     async fn initialize_client<U: BorshSerialize>(
         init_input: U,
         file_path: &str,
@@ -122,6 +155,24 @@ mod integration_tests {
         Ok((alice, contract))
     }
 
+    async fn submit_execution_headers(
+        account: &Account,
+        contract: &Contract,
+        headers: &[eth_types::BlockHeader],
+    ) -> anyhow::Result<()> {
+        for chunk in headers.chunks(100) {
+            let outcome = account
+                .call(contract.id(), "submit_execution_headers")
+                .args_borsh(chunk.to_vec())
+                .gas(Gas::from_tgas(300))
+                .transact()
+                .await?;
+            assert!(outcome.is_success(), "headers submission failed");
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn sepolia_submit_and_verify_update() -> anyhow::Result<()> {
         // Load Sepolia data
@@ -175,4 +226,289 @@ mod integration_tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn sepolia_bounded_gc_after_gc_window_shrink() -> anyhow::Result<()> {
+        let (headers, updates, init_input) = get_sepolia_test_data(Some(InitOptions {
+            validate_updates: false,
+            verify_bls_signatures: false,
+            hashes_gc_threshold: 51_000,
+            trusted_signer: None,
+        }));
+
+        let (alice, contract) = initialize_client(init_input, WASM_FILEPATH).await?;
+        let _ = contract
+            .call("acl_grant_role")
+            .args_json(serde_json::json!({
+                "role": "DAO",
+                "account_id": alice.id().to_string(),
+            }))
+            .transact()
+            .await?;
+
+        let outcome = alice
+            .call(contract.id(), "update_max_gc_entries_per_call")
+            .args_json(serde_json::json!({ "max_gc_entries_per_call": 10 }))
+            .transact()
+            .await?;
+        assert!(outcome.is_success());
+
+        let outcome = alice
+            .call(contract.id(), "submit_beacon_chain_light_client_update")
+            .args_borsh(updates[1].clone())
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success(), "update failed");
+        let reversed_headers: Vec<_> = headers[0][1..].iter().rev().cloned().collect();
+        submit_execution_headers(&alice, &contract, &reversed_headers).await?;
+
+        let outcome = alice
+            .call(contract.id(), "submit_beacon_chain_light_client_update")
+            .args_borsh(updates[2].clone())
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success(), "update failed");
+        let reversed_headers: Vec<_> = headers[1].iter().rev().cloned().collect();
+        let (first_headers, last_headers) = reversed_headers.split_at(reversed_headers.len() - 100);
+        submit_execution_headers(&alice, &contract, first_headers).await?;
+
+        // The DAO shortens the gc window right before the last headers batch, which leaves
+        // a gc backlog far beyond the gc budget of a single submission
+        let hashes_gc_threshold = 9_000;
+        let outcome = alice
+            .call(contract.id(), "update_hashes_gc_threshold")
+            .args_json(serde_json::json!({ "hashes_gc_threshold": hashes_gc_threshold }))
+            .transact()
+            .await?;
+        assert!(outcome.is_success());
+        submit_execution_headers(&alice, &contract, last_headers).await?;
+
+        let last_block_number: u64 = contract.view("last_block_number").await?.borsh()?;
+        assert_eq!(last_block_number, headers[1].last().unwrap().number);
+
+        // The header submission is not bricked by the gc backlog. The blocks left behind the
+        // gc window are still stored, but they can't be proven anymore
+        let status: serde_json::Value = contract.view("get_sync_status").await?.json()?;
+        let gc_lower_bound = status["gc_lower_bound"].as_u64().unwrap();
+        let oldest_provable_block_number = status["oldest_provable_block_number"].as_u64().unwrap();
+        assert_eq!(
+            oldest_provable_block_number,
+            last_block_number + 1 - hashes_gc_threshold
+        );
+        assert!(gc_lower_bound < oldest_provable_block_number);
+
+        let oldest_header = &headers[0][500];
+        let is_known: bool = contract
+            .view("is_known_execution_header")
+            .args_json(serde_json::json!({ "block_number": oldest_header.number }))
+            .await?
+            .borsh()?;
+        assert!(is_known);
+        let result: Option<H256> = contract
+            .view("block_hash_safe")
+            .args_borsh(oldest_header.number)
+            .await?
+            .borsh()?;
+        assert!(result.is_none());
+
+        // Anyone can drain the rest of the backlog
+        let mut gc_backlog = u64::MAX;
+        while gc_backlog > 0 {
+            let outcome = alice
+                .call(contract.id(), "gc_step")
+                .args_json(serde_json::json!({ "max_entries": 1000 }))
+                .gas(Gas::from_tgas(300))
+                .transact()
+                .await?;
+            assert!(outcome.is_success(), "gc step failed");
+            gc_backlog = outcome.borsh()?;
+        }
+
+        let is_known: bool = contract
+            .view("is_known_execution_header")
+            .args_json(serde_json::json!({ "block_number": oldest_header.number }))
+            .await?
+            .borsh()?;
+        assert!(!is_known, "block {} was not removed", oldest_header.number);
+
+        for block_number in [oldest_provable_block_number, last_block_number] {
+            let result: Option<H256> = contract
+                .view("block_hash_safe")
+                .args_borsh(block_number)
+                .await?
+                .borsh()?;
+            assert!(result.is_some(), "block {} missing", block_number);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sepolia_migrate_from_v0_1_0() -> anyhow::Result<()> {
+        let (headers, updates, init_input) = get_sepolia_test_data(Some(InitOptions {
+            validate_updates: false,
+            verify_bls_signatures: false,
+            hashes_gc_threshold: 51_000,
+            trusted_signer: None,
+        }));
+        let init_slot = init_input.finalized_beacon_header.header.slot;
+
+        let (alice, contract) =
+            initialize_client(InitInputV1::from(init_input), V0_1_0_WASM_FILEPATH).await?;
+
+        let wasm = std::fs::read(WASM_FILEPATH)?;
+        let contract = contract.as_account().deploy(&wasm).await?.into_result()?;
+        let state_version: Option<u32> = contract.view("state_version").await?.json()?;
+        assert_eq!(state_version, None);
+
+        let outcome = contract
+            .call("migrate")
+            .args_json(serde_json::json!({ "from_version": 1 }))
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success(), "migration failed");
+
+        let state_version: Option<u32> = contract.view("state_version").await?.json()?;
+        assert_eq!(state_version, Some(STATE_VERSION));
+        let client_mode: ClientMode = contract.view("get_client_mode").await?.borsh()?;
+        assert_eq!(client_mode, ClientMode::SubmitLightClientUpdate);
+        let finalized_slot: u64 = contract
+            .view("finalized_beacon_block_slot")
+            .await?
+            .borsh()?;
+        assert_eq!(finalized_slot, init_slot);
+        let last_block_number: u64 = contract.view("last_block_number").await?.borsh()?;
+        assert_eq!(last_block_number, headers[0][0].number);
+
+        // The contract becomes the super admin of the migrated state
+        let outcome = contract
+            .call("acl_grant_role")
+            .args_json(serde_json::json!({
+                "role": "UnrestrictedSubmitLightClientUpdate",
+                "account_id": alice.id().to_string(),
+            }))
+            .transact()
+            .await?;
+        assert!(outcome.is_success());
+        let outcome = contract
+            .call("acl_grant_role")
+            .args_json(serde_json::json!({
+                "role": "UnrestrictedSubmitExecutionHeader",
+                "account_id": alice.id().to_string(),
+            }))
+            .transact()
+            .await?;
+        assert!(outcome.is_success());
+
+        // The migrated client keeps syncing
+        let outcome = alice
+            .call(contract.id(), "submit_beacon_chain_light_client_update")
+            .args_borsh(updates[1].clone())
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success(), "update failed");
+        let reversed_headers: Vec<_> = headers[0].iter().skip(1).rev().cloned().collect();
+        submit_execution_headers(&alice, &contract, &reversed_headers).await?;
+
+        let last_block_number: u64 = contract.view("last_block_number").await?.borsh()?;
+        assert_eq!(last_block_number, headers[0].last().unwrap().number);
+        let client_mode: ClientMode = contract.view("get_client_mode").await?.borsh()?;
+        assert_eq!(client_mode, ClientMode::SubmitLightClientUpdate);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sepolia_migrate_from_baseline() -> anyhow::Result<()> {
+        let (headers, updates, init_input) = get_sepolia_test_data(Some(InitOptions {
+            validate_updates: false,
+            verify_bls_signatures: false,
+            hashes_gc_threshold: 51_000,
+            trusted_signer: None,
+        }));
+
+        let (alice, contract) =
+            initialize_client(InitInputV2::from(init_input), BASELINE_WASM_FILEPATH).await?;
+
+        // The deployed client has no batched header submission
+        let outcome = alice
+            .call(contract.id(), "submit_beacon_chain_light_client_update")
+            .args_borsh(updates[1].clone())
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success(), "update failed");
+        let reversed_headers: Vec<_> = headers[0].iter().skip(1).rev().cloned().collect();
+        for chunk in reversed_headers[..100].chunks(50) {
+            let mut tx = alice.batch(contract.id());
+            for header in chunk {
+                tx = tx.call(
+                    Function::new("submit_execution_header")
+                        .args(borsh::to_vec(header)?)
+                        .gas(Gas::from_tgas(6)),
+                );
+            }
+            let result = tx.transact().await?;
+            assert!(result.is_success());
+        }
+
+        let wasm = std::fs::read(WASM_FILEPATH)?;
+        let contract = contract.as_account().deploy(&wasm).await?.into_result()?;
+        let state_version: Option<u32> = contract.view("state_version").await?.json()?;
+        assert_eq!(state_version, None);
+
+        let outcome = contract
+            .call("migrate")
+            .args_json(serde_json::json!({ "from_version": 2 }))
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success(), "migration failed");
+
+        let state_version: Option<u32> = contract.view("state_version").await?.json()?;
+        assert_eq!(state_version, Some(STATE_VERSION));
+        let client_mode: ClientMode = contract.view("get_client_mode").await?.borsh()?;
+        assert_eq!(client_mode, ClientMode::SubmitHeader);
+        let finalized_slot: u64 = contract
+            .view("finalized_beacon_block_slot")
+            .await?
+            .borsh()?;
+        assert_eq!(finalized_slot, updates[1].finalized_header.beacon.slot);
+        let last_block_number: u64 = contract.view("last_block_number").await?.borsh()?;
+        assert_eq!(last_block_number, headers[0][0].number);
+        let tail_block_number: Option<u64> = contract
+            .view("get_unfinalized_tail_block_number")
+            .await?
+            .borsh()?;
+        assert_eq!(tail_block_number, Some(reversed_headers[99].number));
+        let block_hash: Option<H256> = contract
+            .view("block_hash_safe")
+            .args_borsh(headers[0][0].number)
+            .await?
+            .borsh()?;
+        assert_eq!(block_hash, Some(headers[0][0].calculate_hash()));
+
+        // The migration of the latest layout is a no-op
+        let outcome = contract
+            .call("migrate")
+            .args_json(serde_json::json!({ "from_version": STATE_VERSION }))
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success(), "migration failed");
+        assert!(outcome.logs().is_empty());
+
+        // The roles granted before the upgrade are kept and the client keeps syncing
+        submit_execution_headers(&alice, &contract, &reversed_headers[100..]).await?;
+        let last_block_number: u64 = contract.view("last_block_number").await?.borsh()?;
+        assert_eq!(last_block_number, headers[0].last().unwrap().number);
+        let client_mode: ClientMode = contract.view("get_client_mode").await?.borsh()?;
+        assert_eq!(client_mode, ClientMode::SubmitLightClientUpdate);
+
+        Ok(())
+    }
 }
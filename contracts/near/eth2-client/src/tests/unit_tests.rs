@@ -6,7 +6,7 @@ mod tests {
     use eth_types::BlockHeader;
     use near_plugins::AccessControllable;
     use near_sdk::test_utils::VMContextBuilder;
-    use near_sdk::{test_vm_config, testing_env, AccountId};
+    use near_sdk::{test_vm_config, testing_env, AccountId, PromiseResult, RuntimeFeesConfig};
 
     macro_rules! inner_set_env {
         ($builder:ident) => {
@@ -76,6 +76,30 @@ mod tests {
         }
     }
 
+    /// Returns the NEP-297 events emitted within the current test context
+    pub fn get_events() -> Vec<serde_json::Value> {
+        near_sdk::test_utils::get_logs()
+            .iter()
+            .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+            .map(|event| serde_json::from_str(event).unwrap())
+            .collect()
+    }
+
+    /// Sets the result of the promise awaited by the callback of the contract
+    pub fn set_promise_result(result: PromiseResult) {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(eth2_client_account())
+            .predecessor_account_id(eth2_client_account());
+        testing_env!(
+            builder.build(),
+            test_vm_config(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
     pub fn submit_and_check_execution_headers(
         contract: &mut Eth2Client,
         headers: Vec<&BlockHeader>,
@@ -92,11 +116,12 @@ mod tests {
         use bitvec::bitarr;
         use bitvec::order::Lsb0;
         use eth2_utility::consensus::*;
+        use eth2_utility::types::{ClientMode, ExecutionBlockRoots, HistoricalBlockProof};
         use eth_types::{H256, U256};
         use hex::FromHex;
         use near_plugins::Pausable;
         use near_sdk::test_utils::accounts;
-        use near_sdk::Gas;
+        use near_sdk::{Gas, NearToken};
         use tree_hash::TreeHash;
 
         #[test]
@@ -112,7 +137,1336 @@ mod tests {
         }
 
         #[test]
-        pub fn test_submit_update_two_periods() {
+        pub fn test_submit_update_two_periods() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+
+            for header in headers[0].iter().skip(1) {
+                let header_hash = header.calculate_hash();
+                assert!(
+                    contract.block_hash_safe(header.number).unwrap_or_default() == header_hash,
+                    "Execution block hash is not finalized: {:?}",
+                    header_hash
+                );
+            }
+
+            assert_eq!(
+                contract.last_block_number(),
+                headers[0].last().unwrap().number
+            );
+        }
+
+        #[test]
+        pub fn test_submit_updates_batch() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_updates(vec![
+                updates[1].clone(),
+                updates[2].clone(),
+            ]);
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                updates[2].finalized_header.beacon.slot
+            );
+            assert_eq!(
+                contract
+                    .get_light_client_state()
+                    .next_sync_committee
+                    .tree_hash_root(),
+                updates[2]
+                    .next_sync_committee
+                    .as_ref()
+                    .unwrap()
+                    .tree_hash_root()
+            );
+
+            let reversed_headers: Vec<BlockHeader> = headers[0]
+                .iter()
+                .skip(1)
+                .chain(headers[1].iter())
+                .rev()
+                .cloned()
+                .collect();
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            assert_eq!(
+                contract.get_client_mode(),
+                ClientMode::SubmitLightClientUpdate
+            );
+            for header in headers[0].iter().chain(headers[1].iter()) {
+                assert_eq!(
+                    contract.block_hash_safe(header.number),
+                    Some(header.calculate_hash())
+                );
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "The acceptable update periods are")]
+        pub fn test_panic_on_submit_updates_batch_with_skipped_period() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_updates(vec![
+                updates[2].clone(),
+                updates[1].clone(),
+            ]);
+        }
+
+        #[test]
+        #[should_panic(expected = "but the `hashes_gc_threshold` is 9500")]
+        pub fn test_panic_on_submit_updates_batch_beyond_gc_threshold() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(Some(InitOptions {
+                validate_updates: true,
+                verify_bls_signatures: true,
+                hashes_gc_threshold: 9500,
+                trusted_signer: None,
+            }));
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_updates(vec![
+                updates[1].clone(),
+                updates[2].clone(),
+            ]);
+        }
+
+        #[test]
+        #[should_panic(expected = "The updates batch is empty")]
+        pub fn test_panic_on_submit_empty_updates_batch() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_updates(vec![]);
+        }
+
+        #[test]
+        pub fn test_events() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter.clone());
+
+            let finalized_slot = contract.finalized_beacon_block_slot();
+            let update = updates[1].clone();
+            contract.submit_beacon_chain_light_client_update(update.clone());
+            let events = get_events();
+            assert!(events.contains(&serde_json::json!({
+                "standard": "eth2-client",
+                "version": "1.0.0",
+                "event": "sync_committee_rotated",
+                "data": {"period": compute_sync_committee_period(update.finalized_header.beacon.slot)},
+            })));
+            let accepted = events
+                .iter()
+                .find(|event| event["event"] == "light_client_update_accepted")
+                .unwrap();
+            assert_eq!(accepted["data"]["old_finalized_slot"], finalized_slot);
+            assert_eq!(
+                accepted["data"]["new_finalized_slot"],
+                update.finalized_header.beacon.slot
+            );
+            assert_eq!(accepted["data"]["forced"], false);
+
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+            let events = get_events();
+            assert!(events.contains(&serde_json::json!({
+                "standard": "eth2-client",
+                "version": "1.0.0",
+                "event": "headers_batch_finalized",
+                "data": {
+                    "start_block": headers[0][1].number,
+                    "end_block": headers[0].last().unwrap().number,
+                    "submitter": submitter,
+                },
+            })));
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.update_hashes_gc_threshold(1000);
+            let events = get_events();
+            let config_updated = events
+                .iter()
+                .find(|event| event["event"] == "config_updated")
+                .unwrap();
+            assert_eq!(config_updated["data"]["parameter"], "hashes_gc_threshold");
+            assert_eq!(
+                config_updated["data"]["config"]["hashes_gc_threshold"],
+                1000
+            );
+        }
+
+        #[test]
+        pub fn test_finalized_beacon_state_roots() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(0));
+
+            let init_header = contract.finalized_beacon_block_header().header;
+            assert_eq!(
+                contract.finalized_beacon_state_root(init_header.slot),
+                Some(init_header.state_root)
+            );
+
+            let finalized_header = &updates[1].finalized_header.beacon;
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            assert_eq!(
+                contract.finalized_beacon_state_root(finalized_header.slot),
+                Some(finalized_header.state_root)
+            );
+
+            // Only the latest state roots are kept
+            let first_slot = finalized_header.slot + 1;
+            for slot in first_slot..first_slot + crate::MAX_FINALIZED_BEACON_STATE_ROOTS - 1 {
+                contract.store_finalized_beacon_state_root(slot, H256::from([0x11; 32]));
+            }
+            assert_eq!(contract.finalized_beacon_state_root(init_header.slot), None);
+            assert_eq!(
+                contract.finalized_beacon_state_root(finalized_header.slot),
+                Some(finalized_header.state_root)
+            );
+
+            contract.store_finalized_beacon_state_root(
+                first_slot + crate::MAX_FINALIZED_BEACON_STATE_ROOTS,
+                H256::from([0x11; 32]),
+            );
+            assert_eq!(
+                contract.finalized_beacon_state_root(finalized_header.slot),
+                None
+            );
+            assert_eq!(
+                contract.finalized_beacon_state_root(first_slot),
+                Some(H256::from([0x11; 32]))
+            );
+        }
+
+        #[test]
+        pub fn test_historical_block_hash() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+
+            let header = updates[1].finalized_header.clone();
+            let block_slot = header.beacon.slot;
+            let block_number = header.execution.block_number;
+            let block_root_branch: Vec<H256> = (0..BLOCK_ROOTS_TREE_DEPTH)
+                .map(|i| H256(ethereum_types::H256::repeat_byte(i as u8)))
+                .collect();
+            let block_roots_root = merkle_root_from_branch(
+                H256(header.beacon.tree_hash_root().0.into()),
+                &block_root_branch,
+                BLOCK_ROOTS_TREE_DEPTH,
+                (block_slot % SLOTS_PER_HISTORICAL_ROOT) as usize,
+            );
+            let network_config = contract.get_network_config();
+            let capella_slot = network_config.get_capella_slot().unwrap();
+
+            // The block is in the `block_roots` of the state
+            let recent_state_slot = block_slot + 100;
+            let state_tree_depth = network_config
+                .get_fork_by_slot(recent_state_slot)
+                .unwrap()
+                .beacon_state_tree_depth() as usize;
+            let block_roots_branch: Vec<H256> = (0..state_tree_depth)
+                .map(|i| H256(ethereum_types::H256::repeat_byte(0x10 + i as u8)))
+                .collect();
+            let recent_state_root = merkle_root_from_branch(
+                block_roots_root,
+                &block_roots_branch,
+                state_tree_depth,
+                BLOCK_ROOTS_FIELD_INDEX as usize,
+            );
+            let recent_proof = HistoricalBlockProof {
+                state_slot: recent_state_slot,
+                header: header.clone(),
+                block_root_branch: block_root_branch.clone(),
+                block_roots_branch,
+            };
+
+            // The block is in the `historical_summaries` of the state
+            let historical_state_slot = block_slot + 3 * SLOTS_PER_HISTORICAL_ROOT;
+            let state_tree_depth = network_config
+                .get_fork_by_slot(historical_state_slot)
+                .unwrap()
+                .beacon_state_tree_depth() as usize;
+            let depth = state_tree_depth + HISTORICAL_SUMMARIES_TREE_DEPTH + 2;
+            let summary_index = (block_slot / SLOTS_PER_HISTORICAL_ROOT
+                - capella_slot / SLOTS_PER_HISTORICAL_ROOT)
+                as usize;
+            let block_summary_branch: Vec<H256> = (0..depth)
+                .map(|i| H256(ethereum_types::H256::repeat_byte(0x40 + i as u8)))
+                .collect();
+            let historical_state_root = merkle_root_from_branch(
+                block_roots_root,
+                &block_summary_branch,
+                depth,
+                ((HISTORICAL_SUMMARIES_FIELD_INDEX as usize)
+                    << (HISTORICAL_SUMMARIES_TREE_DEPTH + 2))
+                    | (summary_index << 1),
+            );
+            let historical_proof = HistoricalBlockProof {
+                state_slot: historical_state_slot,
+                header,
+                block_root_branch,
+                block_roots_branch: block_summary_branch,
+            };
+
+            // The state roots are not stored yet
+            assert_eq!(
+                contract.historical_block_hash_safe(block_number, recent_proof.clone()),
+                None
+            );
+
+            contract
+                .finalized_beacon_state_roots
+                .insert(&recent_state_slot, &recent_state_root);
+            contract
+                .finalized_beacon_state_roots
+                .insert(&historical_state_slot, &historical_state_root);
+
+            let block_hash = Some(updates[1].finalized_header.execution.block_hash);
+            assert_eq!(
+                contract.historical_block_hash_safe(block_number, recent_proof.clone()),
+                block_hash
+            );
+            assert_eq!(
+                contract.historical_block_hash_safe(block_number, historical_proof.clone()),
+                block_hash
+            );
+            assert_eq!(
+                contract.historical_block_hash_safe(block_number + 1, recent_proof.clone()),
+                None
+            );
+
+            // The proof against the state root of another slot is rejected
+            let mut invalid_proof = historical_proof;
+            invalid_proof.state_slot = recent_state_slot;
+            assert_eq!(
+                contract.historical_block_hash_safe(block_number, invalid_proof),
+                None
+            );
+
+            let mut invalid_proof = recent_proof;
+            invalid_proof.block_root_branch[0] = H256::default();
+            assert_eq!(
+                contract.historical_block_hash_safe(block_number, invalid_proof),
+                None
+            );
+        }
+
+        #[test]
+        pub fn test_beacon_state_gindices() {
+            let TestContext {
+                contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+
+            for fork in contract.get_network_config().forks {
+                assert_eq!(
+                    fork.beacon_state_field_gindex("finalized_checkpoint")
+                        .unwrap()
+                        * 2
+                        + 1,
+                    fork.finalized_root_gindex as u64
+                );
+                assert_eq!(
+                    fork.beacon_state_field_gindex("current_sync_committee"),
+                    Some(fork.current_sync_committee_gindex as u64)
+                );
+                assert_eq!(
+                    fork.beacon_state_field_gindex("next_sync_committee"),
+                    Some(fork.next_sync_committee_gindex as u64)
+                );
+            }
+
+            let gindices =
+                contract.get_beacon_state_gindices(contract.finalized_beacon_block_slot());
+            assert_eq!(gindices.len(), BEACON_STATE_FIELDS.len());
+            assert!(gindices.contains(&("validators".to_string(), 75)));
+            assert!(gindices.contains(&("pending_consolidations".to_string(), 100)));
+            assert!(gindices.contains(&("proposer_lookahead".to_string(), 101)));
+
+            let electra_fork = &contract.get_network_config().forks[3];
+            assert_eq!(
+                electra_fork.beacon_state_field_gindex("proposer_lookahead"),
+                None
+            );
+        }
+
+        #[test]
+        pub fn test_verify_beacon_state_field() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+
+            let slot = contract.finalized_beacon_block_slot();
+            let validators_gindex = contract
+                .get_network_config()
+                .get_fork_by_slot(slot)
+                .unwrap()
+                .beacon_state_field_gindex("validators")
+                .unwrap();
+            let gindex =
+                get_validator_field_gindex(validators_gindex, 1234, "withdrawal_credentials")
+                    .unwrap();
+            let depth = floorlog2_u64(gindex);
+            let leaf = H256(ethereum_types::H256::repeat_byte(0x01));
+            let branch: Vec<H256> = (0..depth)
+                .map(|i| H256(ethereum_types::H256::repeat_byte(0x10 + i as u8)))
+                .collect();
+            let state_root =
+                merkle_root_from_branch(leaf, &branch, depth, (gindex - (1 << depth)) as usize);
+            contract
+                .finalized_beacon_state_roots
+                .insert(&slot, &state_root);
+
+            assert!(contract.verify_beacon_state_field(slot, gindex, leaf, branch.clone()));
+            assert!(!contract.verify_beacon_state_field(slot, gindex + 1, leaf, branch.clone()));
+            assert!(!contract.verify_beacon_state_field(
+                slot,
+                gindex,
+                H256::default(),
+                branch.clone()
+            ));
+            assert!(!contract.verify_beacon_state_field(slot + 1, gindex, leaf, branch));
+        }
+
+        fn floorlog2_u64(x: u64) -> usize {
+            (63 - x.leading_zeros()) as usize
+        }
+
+        #[test]
+        pub fn test_block_timestamps() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            let init_header = &headers[0][0];
+            assert_eq!(
+                contract.block_timestamp(init_header.number),
+                Some(init_header.timestamp)
+            );
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            let (unfinalized_headers, last_headers) = reversed_headers.split_at(1000);
+            contract.submit_execution_headers(unfinalized_headers.to_vec());
+
+            // The timestamps of the unfinalized blocks aren't available
+            let unfinalized_header = unfinalized_headers.last().unwrap();
+            assert_eq!(contract.block_timestamp(unfinalized_header.number), None);
+            assert_eq!(
+                contract.block_number_by_timestamp(unfinalized_header.timestamp),
+                Some(init_header.number)
+            );
+
+            for batch in last_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            for header in headers[0].iter() {
+                assert_eq!(
+                    contract.block_timestamp(header.number),
+                    Some(header.timestamp)
+                );
+            }
+
+            let header = &headers[0][100];
+            assert_eq!(
+                contract.block_number_by_timestamp(header.timestamp),
+                Some(header.number)
+            );
+            assert_eq!(
+                contract.block_number_by_timestamp(header.timestamp + 1),
+                Some(header.number)
+            );
+            assert_eq!(
+                contract.block_number_by_timestamp(header.timestamp - 1),
+                Some(header.number - 1)
+            );
+            assert_eq!(
+                contract.block_number_by_timestamp(init_header.timestamp - 1),
+                None
+            );
+            assert_eq!(
+                contract.block_number_by_timestamp(u64::MAX),
+                Some(headers[0].last().unwrap().number)
+            );
+        }
+
+        #[test]
+        pub fn test_block_hashes_range() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            let init_header = &headers[0][0];
+            assert_eq!(
+                contract.finalized_range(),
+                (init_header.number, init_header.number)
+            );
+            assert_eq!(
+                contract.block_hashes_range(init_header.number, 10),
+                vec![(init_header.number, init_header.calculate_hash())]
+            );
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            let last_header = headers[0].last().unwrap();
+            assert_eq!(
+                contract.finalized_range(),
+                (init_header.number, last_header.number)
+            );
+
+            let range = contract.block_hashes_range(headers[0][10].number, 20);
+            let expected_range: Vec<(u64, H256)> = headers[0][10..30]
+                .iter()
+                .map(|header| (header.number, header.calculate_hash()))
+                .collect();
+            assert_eq!(range, expected_range);
+
+            // The range is clamped to the stored blocks and to the limit cap
+            let range = contract.block_hashes_range(init_header.number - 5, 10);
+            assert_eq!(range.len(), 5);
+            assert_eq!(range[0].0, init_header.number);
+            let range = contract.block_hashes_range(last_header.number - 1, 10);
+            assert_eq!(range.len(), 2);
+            let range = contract.block_hashes_range(init_header.number, u64::MAX);
+            assert_eq!(range.len() as u64, crate::MAX_BLOCK_HASHES_RANGE_LIMIT);
+            assert!(contract
+                .block_hashes_range(last_header.number + 1, 10)
+                .is_empty());
+        }
+
+        #[test]
+        pub fn test_aggregate_participant_pubkeys() {
+            let TestContext {
+                contract: _,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            let sync_committee = updates[1].next_sync_committee.clone().unwrap();
+            let sync_committee_keys = Eth2Client::decompress_sync_committee(&sync_committee);
+            let naive_aggregate_key = Eth2Client::decompress_sync_committee(&sync_committee)
+                .pubkeys
+                .chunks(96)
+                .fold(vec![], |mut pks_with_sign, pubkey| {
+                    pks_with_sign.push(0u8);
+                    pks_with_sign.extend_from_slice(pubkey);
+                    pks_with_sign
+                });
+            assert_eq!(
+                near_sdk::env::bls12381_p1_sum(&naive_aggregate_key),
+                sync_committee_keys.aggregate_pubkey
+            );
+
+            for divisor in [2, 3, 5, 512] {
+                let mut sync_committee_bits = bitarr![u8, Lsb0; 0; 512];
+                for i in 0..512 {
+                    sync_committee_bits.set(i, i % divisor != 0);
+                }
+                let sync_committee_bits =
+                    bitvec::vec::BitVec::<u8, Lsb0>::from_slice(sync_committee_bits.as_raw_slice());
+
+                let mut pks_with_sign = vec![];
+                for (pubkey, is_participant) in sync_committee_keys
+                    .pubkeys
+                    .chunks(96)
+                    .zip(sync_committee_bits.iter().by_vals())
+                {
+                    if is_participant {
+                        pks_with_sign.push(0u8);
+                        pks_with_sign.extend_from_slice(pubkey);
+                    }
+                }
+
+                assert_eq!(
+                    Eth2Client::aggregate_participant_pubkeys(
+                        &sync_committee_keys,
+                        &sync_committee_bits
+                    ),
+                    near_sdk::env::bls12381_p1_sum(&pks_with_sign)
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_sync_status() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            let status = contract.get_sync_status();
+            assert_eq!(
+                status.finalized_beacon_slot,
+                contract.finalized_beacon_block_slot()
+            );
+            assert_eq!(
+                status.finalized_period,
+                compute_sync_committee_period(status.finalized_beacon_slot)
+            );
+            assert_eq!(
+                status.finalized_execution_block_number,
+                headers[0][0].number
+            );
+            assert_eq!(status.unfinalized_head_block_number, None);
+            assert_eq!(status.unfinalized_tail_block_number, None);
+            assert_eq!(status.client_mode, ClientMode::SubmitLightClientUpdate);
+            assert_eq!(status.gc_lower_bound, headers[0][0].number);
+            assert_eq!(status.oldest_provable_block_number, headers[0][0].number);
+            assert!(status.next_sync_committee_present);
+            assert!(status.paused.is_empty());
+            assert!(status.config.validate_updates);
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            contract.submit_execution_headers(reversed_headers[..100].to_vec());
+
+            let status = contract.get_sync_status();
+            assert_eq!(
+                status.finalized_beacon_slot,
+                updates[1].finalized_header.beacon.slot
+            );
+            assert_eq!(status.client_mode, ClientMode::SubmitHeader);
+            assert_eq!(
+                status.unfinalized_head_block_number,
+                Some(reversed_headers[0].number)
+            );
+            assert_eq!(
+                status.unfinalized_tail_block_number,
+                Some(reversed_headers[99].number)
+            );
+            assert_eq!(
+                status.finalized_execution_block_number,
+                headers[0][0].number
+            );
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.pa_pause_feature("submit_execution_headers".to_string());
+            assert_eq!(
+                contract.get_sync_status().paused,
+                vec!["submit_execution_headers".to_string()]
+            );
+        }
+
+        #[test]
+        pub fn test_migrate_from_v2_preserves_client_mode() {
+            use crate::migrate::{Eth2ClientV2, STATE_VERSION, STATE_VERSION_KEY};
+            use crate::StorageKey;
+            use near_sdk::collections::{LazyOption, LookupMap};
+            use near_sdk::env;
+
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter.clone());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            contract.submit_execution_headers(reversed_headers[..100].to_vec());
+            assert_eq!(contract.get_client_mode(), ClientMode::SubmitHeader);
+
+            // The collections of v2 are stored under the same keys
+            #[allow(deprecated)]
+            let old_state = Eth2ClientV2 {
+                trusted_signer: contract.trusted_signer.clone(),
+                paused: contract.paused,
+                validate_updates: contract.validate_updates,
+                verify_bls_signatures: contract.verify_bls_signatures,
+                hashes_gc_threshold: contract.hashes_gc_threshold,
+                network: contract.get_config().network,
+                finalized_execution_blocks: LookupMap::new(StorageKey::FinalizedExecutionBlocks),
+                finalized_beacon_header: contract.finalized_beacon_header.clone(),
+                finalized_execution_header: LazyOption::new(
+                    StorageKey::FinalizedExecutionHeader,
+                    None,
+                ),
+                current_sync_committee: LazyOption::new(StorageKey::CurrentSyncCommittee, None),
+                next_sync_committee: LazyOption::new(StorageKey::NextSyncCommittee, None),
+                client_mode: contract.client_mode.clone(),
+                unfinalized_head_execution_header: contract
+                    .unfinalized_head_execution_header
+                    .clone(),
+                unfinalized_tail_execution_header: contract
+                    .unfinalized_tail_execution_header
+                    .clone(),
+                trusted_blocks_submitter: contract.trusted_blocks_submitter.clone(),
+            };
+            env::state_write(&old_state);
+            env::storage_remove(STATE_VERSION_KEY);
+            assert_eq!(Eth2Client::state_version(), None);
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            let contract = Eth2Client::migrate(2);
+            env::state_write(&contract);
+            assert_eq!(Eth2Client::state_version(), Some(STATE_VERSION));
+            assert_eq!(contract.get_client_mode(), ClientMode::SubmitHeader);
+            assert_eq!(
+                contract.get_unfinalized_tail_block_number(),
+                Some(reversed_headers[99].number)
+            );
+            let events = get_events();
+            let event = events.last().unwrap();
+            assert_eq!(event["event"], "state_migrated");
+            assert_eq!(event["data"]["from_version"], 2);
+            assert_eq!(event["data"]["to_version"], STATE_VERSION);
+
+            // The migration of the latest layout doesn't change the state
+            let events_count = get_events().len();
+            let mut contract = Eth2Client::migrate(STATE_VERSION);
+            assert_eq!(contract.get_client_mode(), ClientMode::SubmitHeader);
+            assert_eq!(get_events().len(), events_count);
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            contract.submit_execution_headers(reversed_headers[100..].to_vec());
+            assert_eq!(
+                contract.get_client_mode(),
+                ClientMode::SubmitLightClientUpdate
+            );
+            assert_eq!(
+                contract.last_block_number(),
+                headers[0].last().unwrap().number
+            );
+        }
+
+        #[test]
+        pub fn test_migrate_from_v1() {
+            use crate::migrate::{Eth2ClientV1, STATE_VERSION, STATE_VERSION_KEY};
+            use crate::StorageKey;
+            use eth2_utility::types::ExecutionHeaderInfo;
+            use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap};
+            use near_sdk::env;
+
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter.clone());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+
+            // The finalized beacon header of v1 is ahead of the finalized execution header,
+            // so the headers have to be submitted after the migration
+            #[allow(deprecated)]
+            let mut old_state = Eth2ClientV1 {
+                trusted_signer: contract.trusted_signer.clone(),
+                paused: contract.paused,
+                validate_updates: contract.validate_updates,
+                verify_bls_signatures: contract.verify_bls_signatures,
+                hashes_gc_threshold: contract.hashes_gc_threshold,
+                network: contract.get_config().network,
+                finalized_execution_blocks: LookupMap::new(StorageKey::FinalizedExecutionBlocks),
+                unfinalized_headers: UnorderedMap::new(StorageKey::__DeprecatedUnfinalizedHeaders),
+                submitters: LookupMap::new(StorageKey::__DeprecatedSubmitters),
+                max_submitted_blocks_by_account: 10000,
+                min_storage_balance_for_submitter: 0,
+                finalized_beacon_header: contract.finalized_beacon_header.clone(),
+                finalized_execution_header: LazyOption::new(
+                    StorageKey::FinalizedExecutionHeader,
+                    None,
+                ),
+                current_sync_committee: LazyOption::new(StorageKey::CurrentSyncCommittee, None),
+                next_sync_committee: LazyOption::new(StorageKey::NextSyncCommittee, None),
+            };
+            for header in headers[0].iter().skip(1).take(10) {
+                old_state.unfinalized_headers.insert(
+                    &header.calculate_hash(),
+                    &ExecutionHeaderInfo {
+                        parent_hash: header.parent_hash,
+                        block_number: header.number,
+                        submitter: submitter.clone(),
+                    },
+                );
+            }
+            let unfinalized_headers = borsh::to_vec(&old_state.unfinalized_headers).unwrap();
+            env::state_write(&old_state);
+            env::storage_remove(STATE_VERSION_KEY);
+            assert_eq!(Eth2Client::state_version(), None);
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            let mut contract = Eth2Client::migrate(1);
+            env::state_write(&contract);
+            assert_eq!(Eth2Client::state_version(), Some(STATE_VERSION));
+            assert_eq!(contract.get_client_mode(), ClientMode::SubmitHeader);
+            assert!(contract.get_sync_status().next_sync_committee_present);
+
+            // The storage of the dropped unfinalized headers is released by the gc steps
+            assert_eq!(contract.gc_step(4), 6);
+            assert_eq!(contract.gc_step(10), 0);
+            assert!(contract.unfinalized_headers_v1.is_none());
+            let unfinalized_headers: UnorderedMap<H256, ExecutionHeaderInfo> =
+                borsh::from_slice(&unfinalized_headers).unwrap();
+            for header in headers[0].iter().skip(1).take(10) {
+                assert!(unfinalized_headers.get(&header.calculate_hash()).is_none());
+            }
+            assert_eq!(contract.get_unfinalized_tail_block_number(), None);
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                updates[1].finalized_header.beacon.slot
+            );
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            contract.submit_execution_headers(reversed_headers);
+            assert_eq!(
+                contract.get_client_mode(),
+                ClientMode::SubmitLightClientUpdate
+            );
+            assert_eq!(
+                contract.last_block_number(),
+                headers[0].last().unwrap().number
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "The stored state version is 4")]
+        pub fn test_panic_on_migrate_from_wrong_version() {
+            let TestContext { contract, .. } = get_test_context(None);
+            near_sdk::env::state_write(&contract);
+            Eth2Client::migrate(2);
+        }
+
+        #[test]
+        #[should_panic(expected = "The stored state doesn't match the layout of version 3")]
+        pub fn test_panic_on_migrate_untagged_state_from_wrong_version() {
+            let TestContext { contract, .. } = get_test_context(None);
+            near_sdk::env::state_write(&contract);
+            near_sdk::env::storage_remove(crate::migrate::STATE_VERSION_KEY);
+            Eth2Client::migrate(3);
+        }
+
+        #[test]
+        pub fn test_submitter_stats_and_rewards() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.set_light_client_update_reward(Some(NearToken::from_near(1)));
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                predecessor_account_id: eth2_client_account(),
+                current_account_id: eth2_client_account(),
+                attached_deposit: NearToken::from_millinear(1500),
+            );
+            contract.fund_reward_pool();
+            assert_eq!(
+                contract.get_reward_pool_balance(),
+                NearToken::from_millinear(1500)
+            );
+            assert_eq!(contract.get_submitter_stats(submitter.clone()), None);
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter.clone());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let stats = contract.get_submitter_stats(submitter.clone()).unwrap();
+            assert_eq!(stats.light_client_updates, 1);
+            assert_eq!(stats.execution_headers, 0);
+            assert_eq!(stats.unclaimed_rewards, NearToken::from_near(1));
+            assert_eq!(
+                contract.get_reward_pool_balance(),
+                NearToken::from_millinear(500)
+            );
+
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            contract.submit_execution_headers(reversed_headers.clone());
+            let stats = contract.get_submitter_stats(submitter.clone()).unwrap();
+            assert_eq!(stats.execution_headers, reversed_headers.len() as u64);
+            assert!(stats.gas_burnt > Gas::from_gas(0));
+
+            // The reward pool is not enough to reward the second update
+            contract.submit_beacon_chain_light_client_update(updates[2].clone());
+            let stats = contract.get_submitter_stats(submitter.clone()).unwrap();
+            assert_eq!(stats.light_client_updates, 2);
+            assert_eq!(stats.unclaimed_rewards, NearToken::from_near(1));
+            assert_eq!(
+                contract.get_reward_pool_balance(),
+                NearToken::from_millinear(500)
+            );
+
+            contract.claim_rewards();
+            let stats = contract.get_submitter_stats(submitter.clone()).unwrap();
+            assert_eq!(stats.unclaimed_rewards, NearToken::from_near(0));
+            assert_eq!(stats.claimed_rewards, NearToken::from_near(1));
+
+            // The rewards stay unclaimed if the transfer fails
+            set_promise_result(PromiseResult::Failed);
+            contract.on_rewards_claimed(submitter.clone(), NearToken::from_near(1));
+            let stats = contract.get_submitter_stats(submitter.clone()).unwrap();
+            assert_eq!(stats.unclaimed_rewards, NearToken::from_near(1));
+            assert_eq!(stats.claimed_rewards, NearToken::from_near(0));
+            assert!(get_events().is_empty());
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter.clone());
+            contract.claim_rewards();
+            set_promise_result(PromiseResult::Successful(vec![]));
+            contract.on_rewards_claimed(submitter.clone(), NearToken::from_near(1));
+            let stats = contract.get_submitter_stats(submitter.clone()).unwrap();
+            assert_eq!(stats.unclaimed_rewards, NearToken::from_near(0));
+            assert_eq!(stats.claimed_rewards, NearToken::from_near(1));
+            let events = get_events();
+            let event = events.last().unwrap();
+            assert_eq!(event["event"], "rewards_claimed");
+            assert_eq!(event["data"]["account_id"], submitter.to_string());
+        }
+
+        #[test]
+        pub fn test_withdraw_reward_pool() {
+            let TestContext { mut contract, .. } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                predecessor_account_id: eth2_client_account(),
+                current_account_id: eth2_client_account(),
+                attached_deposit: NearToken::from_near(2),
+            );
+            contract.fund_reward_pool();
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.withdraw_reward_pool(NearToken::from_near(1));
+            assert_eq!(contract.get_reward_pool_balance(), NearToken::from_near(1));
+
+            // The amount is returned to the pool if the transfer fails
+            set_promise_result(PromiseResult::Failed);
+            contract.on_reward_pool_withdrawn(NearToken::from_near(1));
+            assert_eq!(contract.get_reward_pool_balance(), NearToken::from_near(2));
+            assert!(get_events().is_empty());
+
+            contract.withdraw_reward_pool(NearToken::from_near(1));
+            set_promise_result(PromiseResult::Successful(vec![]));
+            contract.on_reward_pool_withdrawn(NearToken::from_near(1));
+            assert_eq!(contract.get_reward_pool_balance(), NearToken::from_near(1));
+            let events = get_events();
+            let event = events.last().unwrap();
+            assert_eq!(event["event"], "reward_pool_withdrawn");
+            assert_eq!(
+                event["data"]["balance"],
+                NearToken::from_near(1).as_yoctonear().to_string()
+            );
+        }
+
+        #[test]
+        pub fn test_submitter_stats_cap() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            contract.tracked_submitters = crate::MAX_TRACKED_SUBMITTERS;
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter.clone());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            assert_eq!(contract.get_submitter_stats(submitter), None);
+            assert_eq!(contract.tracked_submitters, crate::MAX_TRACKED_SUBMITTERS);
+        }
+
+        #[test]
+        #[should_panic(expected = "There are no unclaimed rewards")]
+        pub fn test_panic_on_claim_without_rewards() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(0));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            contract.claim_rewards();
+        }
+
+        /// Returns the block timestamp in nanoseconds at the start of the Sepolia slot
+        fn slot_timestamp(slot: u64) -> u64 {
+            (NetworkConfig::new(&Network::Sepolia).genesis_time + slot * SECONDS_PER_SLOT)
+                * 1_000_000_000
+        }
+
+        #[test]
+        pub fn test_force_update() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.set_force_update_timeout(Some(SLOTS_PER_EPOCH));
+
+            let update = updates[1].clone();
+            let block_timestamp = slot_timestamp(update.signature_slot);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter, block_timestamp: block_timestamp);
+            assert!(contract.submit_best_valid_update(update.clone()));
+            // The update with the same participation doesn't replace the best valid update
+            assert!(!contract.submit_best_valid_update(update.clone()));
+            assert!(contract.get_best_valid_update().is_some());
+
+            // The update finalizes a newer header, so the finalized header is forced
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account(), block_timestamp: block_timestamp);
+            contract.force_update();
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                update.finalized_header.beacon.slot
+            );
+            assert!(get_events().iter().any(|event| {
+                event["event"] == "light_client_update_accepted" && event["data"]["forced"] == true
+            }));
+            assert_eq!(contract.get_client_mode(), ClientMode::SubmitHeader);
+            assert!(contract.get_best_valid_update().is_none());
+            assert_eq!(
+                contract.finalized_beacon_state_root(update.finalized_header.beacon.slot),
+                None
+            );
+
+            // The execution blocks are submitted down from the forced finalized block
+            let finalized_block_number = update.finalized_header.execution.block_number;
+            let reversed_headers: Vec<BlockHeader> = headers[0]
+                .iter()
+                .skip(1)
+                .chain(headers[1].iter())
+                .filter(|header| header.number <= finalized_block_number)
+                .rev()
+                .cloned()
+                .collect();
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            assert_eq!(contract.last_block_number(), finalized_block_number);
+            assert_eq!(
+                contract.get_client_mode(),
+                ClientMode::SubmitLightClientUpdate
+            );
+        }
+
+        #[test]
+        pub fn test_force_update_without_finality() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.set_force_update_timeout(Some(SLOTS_PER_EPOCH));
+
+            // The signature covers only the attested header, so the finality proof can be dropped
+            let mut update = updates[1].clone();
+            update.finality_branch = vec![H256::from([0; 32]); update.finality_branch.len()];
+            let block_timestamp = slot_timestamp(update.signature_slot);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter, block_timestamp: block_timestamp);
+            assert!(contract.submit_best_valid_update(update.clone()));
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account(), block_timestamp: block_timestamp);
+            contract.force_update();
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                update.attested_header.beacon.slot
+            );
+            // The state of the forced header is not finalized
+            assert_eq!(
+                contract.finalized_beacon_state_root(update.attested_header.beacon.slot),
+                None
+            );
+
+            // The execution blocks are submitted down from the forced attested block
+            let attested_block_number = update.attested_header.execution.block_number;
+            let reversed_headers: Vec<BlockHeader> = headers[0]
+                .iter()
+                .skip(1)
+                .chain(headers[1].iter())
+                .filter(|header| header.number <= attested_block_number)
+                .rev()
+                .cloned()
+                .collect();
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            assert_eq!(contract.last_block_number(), attested_block_number);
+        }
+
+        #[test]
+        #[should_panic(expected = "is ahead of the current slot")]
+        pub fn test_panic_on_force_update_with_inflated_signature_slot() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            let mut update = updates[1].clone();
+            let current_slot = update.signature_slot;
+
+            // The timeout passes right after the current slot
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.set_force_update_timeout(Some(
+                current_slot - contract.finalized_beacon_block_slot(),
+            ));
+
+            // The signature stays valid with any signature slot of the same period, so the last
+            // slot of the period would open the force update before the timeout
+            let signature_period = compute_sync_committee_period(update.signature_slot);
+            update.signature_slot =
+                (signature_period + 1) * SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD - 1;
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter, block_timestamp: slot_timestamp(current_slot));
+            contract.submit_best_valid_update(update);
+        }
+
+        /// Returns the update signed by the first `participants` members of the sync committee
+        fn with_participation(
+            update: &LightClientUpdate,
+            participants: usize,
+        ) -> LightClientUpdate {
+            let mut update = update.clone();
+            for (index, byte) in update
+                .sync_aggregate
+                .sync_committee_bits
+                .0
+                .iter_mut()
+                .enumerate()
+            {
+                *byte = (0..8)
+                    .filter(|bit| index * 8 + bit < participants)
+                    .fold(0, |byte, bit| byte | (1 << bit));
+            }
+            update
+        }
+
+        #[test]
+        pub fn test_is_better_update() {
+            let TestContext {
+                contract: _,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            let update = with_participation(&updates[1], 400);
+
+            // The supermajority wins regardless of the participation
+            assert!(Eth2Client::is_better_update(
+                &with_participation(&update, 342),
+                &with_participation(&update, 341)
+            ));
+            assert!(!Eth2Client::is_better_update(
+                &with_participation(&update, 341),
+                &with_participation(&update, 342)
+            ));
+            // Without the supermajority the participation wins
+            assert!(Eth2Client::is_better_update(
+                &with_participation(&update, 300),
+                &with_participation(&update, 200)
+            ));
+
+            // The finality wins over the participation beyond the supermajority
+            let mut without_finality = with_participation(&update, 500);
+            without_finality.finality_branch =
+                vec![H256::from([0; 32]); update.finality_branch.len()];
+            assert!(Eth2Client::is_better_update(&update, &without_finality));
+            assert!(!Eth2Client::is_better_update(&without_finality, &update));
+
+            // The participation beyond the supermajority is the first tiebreaker
+            assert!(Eth2Client::is_better_update(
+                &with_participation(&update, 500),
+                &update
+            ));
+
+            // The older data is the second tiebreaker
+            let mut later_signed = update.clone();
+            later_signed.signature_slot += 1;
+            assert!(Eth2Client::is_better_update(&update, &later_signed));
+            assert!(!Eth2Client::is_better_update(&later_signed, &update));
+            assert!(!Eth2Client::is_better_update(&update, &update));
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid sync committee bits sum: 0")]
+        pub fn test_panic_on_submit_best_valid_update_without_participants() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(Some(InitOptions {
+                validate_updates: false,
+                verify_bls_signatures: false,
+                hashes_gc_threshold: 51_000,
+                trusted_signer: None,
+            }));
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.set_force_update_timeout(Some(SLOTS_PER_EPOCH));
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(0), block_timestamp: slot_timestamp(updates[1].signature_slot));
+            contract.submit_best_valid_update(with_participation(&updates[1], 0));
+        }
+
+        #[test]
+        #[should_panic(expected = "The force update is allowed after the slot")]
+        pub fn test_panic_on_force_update_before_timeout() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.set_force_update_timeout(Some(
+                SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD * 2,
+            ));
+
+            let block_timestamp = slot_timestamp(updates[1].signature_slot);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter, block_timestamp: block_timestamp);
+            contract.submit_best_valid_update(updates[1].clone());
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account(), block_timestamp: block_timestamp);
+            contract.force_update();
+        }
+
+        #[test]
+        #[should_panic(expected = "The force update is disabled")]
+        pub fn test_panic_on_submit_best_valid_update_when_disabled() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(0));
+
+            contract.submit_best_valid_update(updates[1].clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "Insufficient permissions")]
+        pub fn test_panic_on_force_update_without_dao_role() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(0));
+
+            contract.force_update();
+        }
+
+        #[test]
+        pub fn test_submit_execution_headers_batch() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            for header in headers[0].iter().skip(1) {
+                assert_eq!(
+                    contract.block_hash_safe(header.number),
+                    Some(header.calculate_hash()),
+                    "Execution block hash is not finalized: {:?}",
+                    header.number
+                );
+            }
+
+            assert_eq!(
+                contract.last_block_number(),
+                headers[0].last().unwrap().number
+            );
+            assert_eq!(
+                contract.get_client_mode(),
+                ClientMode::SubmitLightClientUpdate
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "The expected block hash is")]
+        pub fn test_panic_on_submit_execution_headers_batch_with_gap() {
             let submitter = accounts(0);
             let TestContext {
                 mut contract,
@@ -123,24 +1477,59 @@ mod tests {
 
             contract.submit_beacon_chain_light_client_update(updates[1].clone());
 
+            let mut batch: Vec<BlockHeader> = headers[0].iter().rev().take(10).cloned().collect();
+            batch.remove(5);
+            contract.submit_execution_headers(batch);
+        }
+
+        #[test]
+        #[should_panic(expected = "The headers batch is empty")]
+        pub fn test_panic_on_submit_empty_execution_headers_batch() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            contract.submit_execution_headers(vec![]);
+        }
+
+        #[test]
+        pub fn test_store_execution_roots() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.set_store_execution_roots(true);
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+
+            let head = headers[0].last().unwrap();
+            contract.submit_execution_header(head.clone());
+            assert!(contract.execution_block_roots_safe(head.number).is_none());
+
             submit_and_check_execution_headers(
                 &mut contract,
-                headers[0].iter().skip(1).rev().collect(),
+                headers[0].iter().skip(1).rev().skip(1).collect(),
             );
 
             for header in headers[0].iter().skip(1) {
-                let header_hash = header.calculate_hash();
-                assert!(
-                    contract.block_hash_safe(header.number).unwrap_or_default() == header_hash,
-                    "Execution block hash is not finalized: {:?}",
-                    header_hash
+                assert_eq!(
+                    contract.execution_block_roots_safe(header.number),
+                    Some(ExecutionBlockRoots::from(header))
                 );
             }
-
-            assert_eq!(
-                contract.last_block_number(),
-                headers[0].last().unwrap().number
-            );
+            assert!(contract
+                .execution_block_roots_safe(headers[0][0].number)
+                .is_none());
         }
 
         #[test]
@@ -220,7 +1609,307 @@ mod tests {
                     "Execution block was not removed: {:?}",
                     header.number
                 );
+                assert!(contract.block_timestamp(header.number).is_none());
+            }
+
+            let (lowest, highest) = contract.finalized_range();
+            assert_eq!(highest, headers[1].last().unwrap().number);
+            assert!(contract.block_hash_safe(lowest).is_some());
+            assert!(contract.block_hash_safe(lowest - 1).is_none());
+            let range = contract.block_hashes_range(lowest - 5, 6);
+            assert_eq!(
+                range,
+                vec![(lowest, contract.block_hash_safe(lowest).unwrap())]
+            );
+
+            // The timestamps of the garbage collected blocks can't be resolved
+            let oldest_header = headers.concat()[0].clone();
+            assert_eq!(
+                contract.block_number_by_timestamp(oldest_header.timestamp),
+                None
+            );
+        }
+
+        #[test]
+        pub fn test_gc_catches_up_with_large_batches() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(Some(InitOptions {
+                validate_updates: true,
+                verify_bls_signatures: true,
+                hashes_gc_threshold: 9500,
+                trusted_signer: None,
+            }));
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+
+            // Each batch is larger than the default gc margin
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            contract.submit_beacon_chain_light_client_update(updates[2].clone());
+            let reversed_headers: Vec<BlockHeader> = headers[1].iter().rev().cloned().collect();
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+                assert_eq!(contract.gc_step(0), 0);
+            }
+
+            // The blocks behind the gc window are removed without the gc steps
+            assert!(contract.gc_target > headers[0][500].number);
+            assert_eq!(contract.gc_cursor, contract.gc_target);
+            for header in headers.concat() {
+                assert_eq!(
+                    contract.is_known_execution_header(header.number),
+                    header.number >= contract.gc_cursor
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_bounded_gc_and_gc_step() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.update_max_gc_entries_per_call(10);
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let reversed_headers: Vec<BlockHeader> =
+                headers[0].iter().skip(1).rev().cloned().collect();
+            for batch in reversed_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            contract.submit_beacon_chain_light_client_update(updates[2].clone());
+            let reversed_headers: Vec<BlockHeader> = headers[1].iter().rev().cloned().collect();
+            let (first_headers, last_headers) =
+                reversed_headers.split_at(reversed_headers.len() - 100);
+            for batch in first_headers.chunks(1000) {
+                contract.submit_execution_headers(batch.to_vec());
+            }
+
+            // The DAO shortens the gc window right before the last headers batch, which leaves
+            // a gc backlog far beyond the gc budget of a single submission
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.update_hashes_gc_threshold(9000);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            contract.submit_execution_headers(last_headers.to_vec());
+
+            // The gc applied on submission is bounded, so the old blocks are still stored,
+            // but they are outside of the gc window and can't be proven anymore
+            let old_header = &headers[0][500];
+            assert!(contract.is_known_execution_header(old_header.number));
+            assert!(contract.block_hash_safe(old_header.number).is_none());
+            assert!(contract.get_sync_status().oldest_provable_block_number > old_header.number);
+
+            // Anyone can process the rest of the gc backlog step by step
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(1));
+            let mut gc_backlog = contract.gc_step(100);
+            assert!(gc_backlog > 0);
+            while gc_backlog > 0 {
+                gc_backlog = contract.gc_step(1000);
+            }
+
+            assert!(!contract.is_known_execution_header(old_header.number));
+            for header in headers[1].iter() {
+                assert_eq!(
+                    contract.block_hash_safe(header.number),
+                    Some(header.calculate_hash())
+                );
             }
+            assert_eq!(contract.gc_step(1000), 0);
+        }
+
+        #[test]
+        pub fn test_add_fork() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+
+            let fork = ForkConfig::fulu([0x90, 0x00, 0x00, 0x76], 10_000_000);
+            contract.add_fork(fork.clone());
+            assert_eq!(contract.get_network_config().forks.last(), Some(&fork));
+
+            // Several forks can be activated at the same epoch, the last one is applied
+            let fork = ForkConfig::fulu([0x90, 0x00, 0x00, 0x77], 10_000_000);
+            contract.add_fork(fork.clone());
+            assert_eq!(contract.get_network_config().forks.last(), Some(&fork));
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: submitter);
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "The fork epochs should not decrease, but got 272639")]
+        pub fn test_panic_on_add_fork_before_last_fork() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+
+            contract.add_fork(ForkConfig::fulu([0x90, 0x00, 0x00, 0x76], 272639));
+        }
+
+        #[test]
+        #[should_panic(expected = "The fork epoch should be higher than the finalized epoch")]
+        pub fn test_panic_on_add_fork_before_finalized_epoch() {
+            let (_headers, _updates, mut init_input) = get_test_data(None);
+            let mut network_config = NetworkConfig::new(&init_input.network);
+            network_config.forks.pop();
+            init_input.network = Network::Custom(network_config);
+
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+
+            let finalized_epoch = compute_epoch_at_slot(contract.finalized_beacon_block_slot());
+            contract.add_fork(ForkConfig::fulu([0x90, 0x00, 0x00, 0x75], finalized_epoch));
+        }
+
+        #[test]
+        pub fn test_init_with_custom_network() {
+            let (headers, updates, mut init_input) = get_test_data(None);
+            init_input.network = Network::Custom(NetworkConfig::new(&init_input.network));
+
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            assert_eq!(
+                contract.get_network_config(),
+                NetworkConfig::new(&Network::Sepolia)
+            );
+            assert_eq!(contract.get_config().network, Network::Sepolia);
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+        }
+
+        #[test]
+        pub fn test_init_from_bootstrap() {
+            let (headers, updates, init_input) = get_test_bootstrap_data();
+            let bootstrap_slot = init_input.bootstrap.header.beacon.slot;
+            let bootstrap_block_number = init_input.finalized_execution_header.number;
+
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init_from_bootstrap(init_input);
+            assert_eq!(contract.finalized_beacon_block_slot(), bootstrap_slot);
+            assert_eq!(contract.last_block_number(), bootstrap_block_number);
+
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0]
+                    .iter()
+                    .filter(|header| header.number > bootstrap_block_number)
+                    .rev()
+                    .collect(),
+            );
+            assert_eq!(
+                contract.get_client_mode(),
+                ClientMode::SubmitLightClientUpdate
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "The bootstrap header doesn't match the trusted block root")]
+        pub fn test_panic_on_init_from_bootstrap_with_untrusted_root() {
+            let (_headers, _updates, mut init_input) = get_test_bootstrap_data();
+            init_input.trusted_block_root = H256::default();
+
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            Eth2Client::init_from_bootstrap(init_input);
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid current sync committee proof")]
+        pub fn test_panic_on_init_from_bootstrap_with_invalid_committee_branch() {
+            let (_headers, _updates, mut init_input) = get_test_bootstrap_data();
+            init_input.bootstrap.current_sync_committee_branch[1] = H256::default();
+
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            Eth2Client::init_from_bootstrap(init_input);
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "The update should be finalized, attested and signed in the bootstrap period"
+        )]
+        pub fn test_panic_on_init_from_bootstrap_with_update_of_another_period() {
+            let (_headers, updates, mut init_input) = get_test_bootstrap_data();
+            init_input.update = updates[1].clone();
+
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            Eth2Client::init_from_bootstrap(init_input);
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid finality proof")]
+        pub fn test_panic_on_update_with_outdated_fork_schedule() {
+            let (_headers, updates, mut init_input) = get_test_data(None);
+            // The schedule without the Electra fork uses outdated generalized indices
+            let mut network_config = NetworkConfig::new(&init_input.network);
+            network_config.forks.truncate(3);
+            init_input.network = Network::Custom(network_config);
+
+            set_env!(
+                prepaid_gas: Gas::from_tgas(1_000_000),
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
         }
 
         #[test]
@@ -405,6 +2094,180 @@ mod tests {
             contract.submit_beacon_chain_light_client_update(updates[1].clone());
         }
 
+        #[test]
+        #[should_panic(expected = "The updates don't finalize different headers at the same slot")]
+        pub fn test_panic_on_report_not_conflicting_updates() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(1));
+
+            contract.report_conflicting_update(updates[1].clone(), updates[1].clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid finality proof")]
+        pub fn test_panic_on_report_conflicting_update_with_invalid_finality_proof() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(1));
+
+            let mut forged_update = updates[1].clone();
+            forged_update.finalized_header.beacon.proposer_index += 1;
+            contract.report_conflicting_update(updates[1].clone(), forged_update);
+        }
+
+        #[test]
+        pub fn test_report_conflicting_update() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(Some(InitOptions {
+                validate_updates: true,
+                verify_bls_signatures: false,
+                hashes_gc_threshold: 51_000,
+                trusted_signer: Some(accounts(1)),
+            }));
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(1));
+
+            // The attested state commits to another header finalized at the same slot
+            let first_update = updates[1].clone();
+            let mut second_update = first_update.clone();
+            second_update.finalized_header.beacon.proposer_index += 1;
+            let generalized_index = contract
+                .get_network_config()
+                .get_generalized_index_constants(second_update.finalized_header.beacon.slot)
+                .unwrap();
+            second_update.attested_header.beacon.state_root = merkle_root_from_branch(
+                H256(
+                    second_update
+                        .finalized_header
+                        .beacon
+                        .tree_hash_root()
+                        .0
+                        .into(),
+                ),
+                &second_update.finality_branch,
+                generalized_index.finality_tree_depth as usize,
+                generalized_index.finality_tree_index as usize,
+            );
+
+            contract.report_conflicting_update(first_update.clone(), second_update.clone());
+            assert!(contract.get_sync_status().submissions_halted);
+            let report_events: Vec<serde_json::Value> = get_events()
+                .into_iter()
+                .filter(|event| event["event"] == "conflicting_updates_reported")
+                .collect();
+            assert_eq!(report_events.len(), 1);
+            assert_eq!(
+                report_events[0]["data"]["reporter"],
+                accounts(1).to_string()
+            );
+
+            let evidence = contract.get_conflicting_updates_evidence().unwrap();
+            assert_eq!(
+                evidence
+                    .first_update
+                    .finalized_header
+                    .beacon
+                    .tree_hash_root(),
+                first_update.finalized_header.beacon.tree_hash_root()
+            );
+            assert_eq!(
+                evidence
+                    .second_update
+                    .finalized_header
+                    .beacon
+                    .tree_hash_root(),
+                second_update.finalized_header.beacon.tree_hash_root()
+            );
+            assert_eq!(evidence.reporter, accounts(1));
+        }
+
+        #[test]
+        #[should_panic(expected = "Only the trusted signer can report the updates")]
+        pub fn test_panic_on_report_conflicting_update_without_bls_verification() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(Some(InitOptions {
+                validate_updates: true,
+                verify_bls_signatures: false,
+                hashes_gc_threshold: 51_000,
+                trusted_signer: None,
+            }));
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(1));
+
+            let mut second_update = updates[1].clone();
+            second_update.finalized_header.beacon.proposer_index += 1;
+            contract.report_conflicting_update(updates[1].clone(), second_update);
+        }
+
+        #[test]
+        pub fn test_resume_submissions_halted_by_conflicting_updates() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            contract.submissions_halted = true;
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: eth2_client_account(), current_account_id: eth2_client_account());
+            contract.acl_grant_role(crate::Role::DAO.into(), eth2_client_account());
+            contract.resume_submissions();
+            assert!(!contract.get_sync_status().submissions_halted);
+            let resume_events: Vec<serde_json::Value> = get_events()
+                .into_iter()
+                .filter(|event| event["event"] == "submissions_resumed")
+                .collect();
+            assert_eq!(resume_events.len(), 1);
+
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(0));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "The submissions are halted by the reported conflicting updates")]
+        pub fn test_panic_on_submit_update_halted() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(0));
+
+            contract.submissions_halted = true;
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "The conflicting updates are already reported")]
+        pub fn test_panic_on_report_conflicting_update_with_recorded_evidence() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: Gas::from_tgas(1_000_000), predecessor_account_id: accounts(1));
+
+            contract.conflicting_updates_evidence.set(
+                &eth2_utility::types::ConflictingUpdatesEvidence {
+                    first_update: updates[1].clone(),
+                    second_update: updates[1].clone(),
+                    reporter: accounts(1),
+                    reported_at: 0,
+                },
+            );
+            contract.report_conflicting_update(updates[1].clone(), updates[1].clone());
+        }
+
         #[test]
         #[should_panic(
             expected = "The active header slot number should be higher than the finalized slot"
@@ -1,11 +1,12 @@
 use std::str::FromStr;
 
 use eth2_utility::consensus::Network;
-use eth2_utility::types::InitInput;
+use eth2_utility::types::{BootstrapInitInput, InitInput};
 use eth_types::eth2::*;
-use eth_types::BlockHeader;
+use eth_types::{BlockHeader, H256};
 use lazy_static::lazy_static;
 use near_sdk::AccountId;
+use tree_hash::TreeHash;
 
 pub fn read_beacon_header(filename: String) -> BeaconBlockHeader {
     serde_json::from_reader(std::fs::File::open(std::path::Path::new(&filename)).unwrap()).unwrap()
@@ -105,3 +106,43 @@ pub fn get_test_data(
 ) {
     get_sepolia_test_data(init_options)
 }
+
+/// Builds the bootstrap of the attested header of the first update. The current sync committee
+/// branch is derived from the next sync committee branch, since the committees are siblings in
+/// the beacon state.
+pub fn get_test_bootstrap_data() -> (
+    &'static Vec<Vec<BlockHeader>>,
+    &'static Vec<LightClientUpdate>,
+    BootstrapInitInput,
+) {
+    let (headers, updates, init_input) = get_test_data(None);
+    let update = &updates[0];
+
+    let next_sync_committee = update.next_sync_committee.as_ref().unwrap();
+    let mut current_sync_committee_branch = update.next_sync_committee_branch.clone().unwrap();
+    current_sync_committee_branch[0] = H256(next_sync_committee.tree_hash_root().0.into());
+
+    let header = update.attested_header.clone();
+    let finalized_execution_header = headers[0]
+        .iter()
+        .find(|execution_header| execution_header.number == header.execution.block_number)
+        .expect("Execution header not found")
+        .clone();
+
+    let init_input = BootstrapInitInput {
+        network: init_input.network,
+        trusted_block_root: H256(header.beacon.tree_hash_root().0.into()),
+        bootstrap: LightClientBootstrap {
+            header,
+            current_sync_committee: init_input.current_sync_committee,
+            current_sync_committee_branch,
+        },
+        update: update.clone(),
+        finalized_execution_header,
+        validate_updates: true,
+        hashes_gc_threshold: 51000,
+        trusted_signer: None,
+    };
+
+    (headers, updates, init_input)
+}
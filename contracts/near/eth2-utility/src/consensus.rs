@@ -2,16 +2,88 @@ use std::str::FromStr;
 
 use bitvec::order::Lsb0;
 use bitvec::prelude::BitVec;
-use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use eth_types::eth2::*;
 use eth_types::H256;
 use near_sdk::near;
+
+use crate::types::HistoricalBlockProof;
 use tree_hash::TreeHash;
 
 pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
 pub const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64 = 1;
 pub const SLOTS_PER_EPOCH: u64 = 32;
+pub const SECONDS_PER_SLOT: u64 = 12;
 pub const DOMAIN_SYNC_COMMITTEE: DomainType = [0x07, 0x00, 0x00, 0x00];
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+/// Depth of the `block_roots` vector of `SLOTS_PER_HISTORICAL_ROOT` roots
+pub const BLOCK_ROOTS_TREE_DEPTH: usize = 13;
+/// Depth of the `historical_summaries` list of `HISTORICAL_ROOTS_LIMIT` items
+pub const HISTORICAL_SUMMARIES_TREE_DEPTH: usize = 24;
+/// Index of the `block_roots` field in the `BeaconState`
+pub const BLOCK_ROOTS_FIELD_INDEX: u32 = 5;
+/// Index of the `historical_summaries` field in the `BeaconState`, added in Capella
+pub const HISTORICAL_SUMMARIES_FIELD_INDEX: u32 = 27;
+/// Depth of the `validators` and `balances` lists of `VALIDATOR_REGISTRY_LIMIT` items
+pub const VALIDATOR_REGISTRY_LIMIT_DEPTH: u32 = 40;
+
+/// Fields of the `BeaconState` in the container order.
+/// Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/fulu/beacon-chain.md#beaconstate
+pub const BEACON_STATE_FIELDS: [&str; 38] = [
+    "genesis_time",
+    "genesis_validators_root",
+    "slot",
+    "fork",
+    "latest_block_header",
+    "block_roots",
+    "state_roots",
+    "historical_roots",
+    "eth1_data",
+    "eth1_data_votes",
+    "eth1_deposit_index",
+    "validators",
+    "balances",
+    "randao_mixes",
+    "slashings",
+    "previous_epoch_participation",
+    "current_epoch_participation",
+    "justification_bits",
+    "previous_justified_checkpoint",
+    "current_justified_checkpoint",
+    "finalized_checkpoint",
+    "inactivity_scores",
+    "current_sync_committee",
+    "next_sync_committee",
+    // Bellatrix
+    "latest_execution_payload_header",
+    // Capella
+    "next_withdrawal_index",
+    "next_withdrawal_validator_index",
+    "historical_summaries",
+    // Electra
+    "deposit_requests_start_index",
+    "deposit_balance_to_consume",
+    "exit_balance_to_consume",
+    "earliest_exit_epoch",
+    "consolidation_balance_to_consume",
+    "earliest_consolidation_epoch",
+    "pending_deposits",
+    "pending_partial_withdrawals",
+    "pending_consolidations",
+    // Fulu
+    "proposer_lookahead",
+];
+
+/// Fields of the `Validator` container in the container order
+pub const VALIDATOR_FIELDS: [&str; 8] = [
+    "pubkey",
+    "withdrawal_credentials",
+    "effective_balance",
+    "slashed",
+    "activation_eligibility_epoch",
+    "activation_epoch",
+    "exit_epoch",
+    "withdrawable_epoch",
+];
 
 #[derive(Debug)]
 pub struct GeneralizedIndex {
@@ -25,12 +97,14 @@ pub struct GeneralizedIndex {
     pub execution_payload_tree_index: u32,
 }
 
-#[derive(PartialEq, BorshSerialize, BorshDeserialize, BorshSchema, Clone, Copy, Debug)]
-#[near(serializers=[json])]
+#[derive(PartialEq, Clone, Debug)]
+#[near(serializers=[borsh, json])]
 pub enum Network {
     Mainnet,
     Goerli,
     Sepolia,
+    /// Network which is not known to the client, e.g. Holesky, Hoodi or a local devnet
+    Custom(NetworkConfig),
 }
 
 impl FromStr for Network {
@@ -45,22 +119,164 @@ impl FromStr for Network {
     }
 }
 
+/// Layout of the `ExecutionPayloadHeader` used by a fork
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[near(serializers=[borsh, json])]
+pub enum ExecutionPayloadLayout {
+    Bellatrix,
+    /// Adds `withdrawals_root`
+    Capella,
+    /// Adds `blob_gas_used` and `excess_blob_gas`
+    Deneb,
+}
+
+/// Layout of the `BeaconState` used by a fork, the forks missing here didn't add any fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[near(serializers=[borsh, json])]
+pub enum BeaconStateLayout {
+    Bellatrix,
+    /// Adds the withdrawal fields and `historical_summaries`
+    Capella,
+    /// Adds the execution requests fields, the tree depth grows to 6
+    Electra,
+    /// Adds `proposer_lookahead`
+    Fulu,
+}
+
+impl BeaconStateLayout {
+    /// Number of the `BeaconState` fields of the layout
+    pub const fn fields_count(&self) -> usize {
+        match self {
+            Self::Bellatrix => 25,
+            Self::Capella => 28,
+            Self::Electra => 37,
+            Self::Fulu => 38,
+        }
+    }
+}
+
+/// Fork parameters used by the light client starting from the fork activation epoch
+#[derive(Clone, Debug, PartialEq)]
+#[near(serializers=[borsh, json])]
+pub struct ForkConfig {
+    pub version: ForkVersion,
+    pub epoch: Epoch,
+    pub execution_payload_layout: ExecutionPayloadLayout,
+    pub beacon_state_layout: BeaconStateLayout,
+    pub finalized_root_gindex: u32,
+    pub current_sync_committee_gindex: u32,
+    pub next_sync_committee_gindex: u32,
+    pub execution_payload_gindex: u32,
+}
+
+impl ForkConfig {
+    // Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md
+    pub const FINALIZED_ROOT_GINDEX: u32 = 105;
+    pub const CURRENT_SYNC_COMMITTEE_GINDEX: u32 = 54;
+    pub const NEXT_SYNC_COMMITTEE_GINDEX: u32 = 55;
+
+    // Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/electra/light-client/sync-protocol.md
+    pub const FINALIZED_ROOT_GINDEX_ELECTRA: u32 = 169;
+    pub const CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA: u32 = 86;
+    pub const NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA: u32 = 87;
+
+    // Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/capella/light-client/sync-protocol.md
+    pub const EXECUTION_PAYLOAD_GINDEX: u32 = 25;
+
+    /// Fork using the light client generalized indices introduced in Altair
+    pub const fn altair(
+        version: ForkVersion,
+        epoch: Epoch,
+        execution_payload_layout: ExecutionPayloadLayout,
+    ) -> Self {
+        Self {
+            version,
+            epoch,
+            execution_payload_layout,
+            beacon_state_layout: match execution_payload_layout {
+                ExecutionPayloadLayout::Bellatrix => BeaconStateLayout::Bellatrix,
+                ExecutionPayloadLayout::Capella | ExecutionPayloadLayout::Deneb => {
+                    BeaconStateLayout::Capella
+                }
+            },
+            finalized_root_gindex: Self::FINALIZED_ROOT_GINDEX,
+            current_sync_committee_gindex: Self::CURRENT_SYNC_COMMITTEE_GINDEX,
+            next_sync_committee_gindex: Self::NEXT_SYNC_COMMITTEE_GINDEX,
+            execution_payload_gindex: Self::EXECUTION_PAYLOAD_GINDEX,
+        }
+    }
+
+    /// Depth of the `BeaconState` tree. The finalized root is the `root` of the
+    /// `finalized_checkpoint` field, so it's one level below the state fields.
+    pub const fn beacon_state_tree_depth(&self) -> u32 {
+        floorlog2(self.finalized_root_gindex) - 1
+    }
+
+    /// Number of the `BeaconState` fields of the fork
+    pub fn beacon_state_fields_count(&self) -> usize {
+        self.beacon_state_layout.fields_count()
+    }
+
+    /// Generalized index of the named `BeaconState` field
+    pub fn beacon_state_field_gindex(&self, field: &str) -> Option<u64> {
+        let field_index = BEACON_STATE_FIELDS[..self.beacon_state_fields_count()]
+            .iter()
+            .position(|name| *name == field)?;
+        Some((1u64 << self.beacon_state_tree_depth()) + field_index as u64)
+    }
+
+    /// Generalized indices of all the `BeaconState` fields of the fork
+    pub fn beacon_state_gindices(&self) -> Vec<(String, u64)> {
+        BEACON_STATE_FIELDS[..self.beacon_state_fields_count()]
+            .iter()
+            .enumerate()
+            .map(|(field_index, name)| {
+                (
+                    name.to_string(),
+                    (1u64 << self.beacon_state_tree_depth()) + field_index as u64,
+                )
+            })
+            .collect()
+    }
+
+    /// Fork using the light client generalized indices introduced in Electra
+    pub const fn electra(version: ForkVersion, epoch: Epoch) -> Self {
+        Self {
+            version,
+            epoch,
+            execution_payload_layout: ExecutionPayloadLayout::Deneb,
+            beacon_state_layout: BeaconStateLayout::Electra,
+            finalized_root_gindex: Self::FINALIZED_ROOT_GINDEX_ELECTRA,
+            current_sync_committee_gindex: Self::CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+            next_sync_committee_gindex: Self::NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+            execution_payload_gindex: Self::EXECUTION_PAYLOAD_GINDEX,
+        }
+    }
+
+    /// Fork with the Electra light client generalized indices and the Fulu `BeaconState`
+    pub const fn fulu(version: ForkVersion, epoch: Epoch) -> Self {
+        Self {
+            beacon_state_layout: BeaconStateLayout::Fulu,
+            ..Self::electra(version, epoch)
+        }
+    }
+}
+
+/// Genesis parameters and fork schedule of the network
+#[derive(Clone, Debug, PartialEq)]
+#[near(serializers=[borsh, json])]
 pub struct NetworkConfig {
     pub genesis_validators_root: [u8; 32],
-    pub bellatrix_fork_version: ForkVersion,
-    pub bellatrix_fork_epoch: u64,
-    pub capella_fork_version: ForkVersion,
-    pub capella_fork_epoch: u64,
-    pub deneb_fork_version: ForkVersion,
-    pub deneb_fork_epoch: u64,
-    pub electra_fork_version: ForkVersion,
-    pub electra_fork_epoch: u64,
-    pub fulu_fork_version: ForkVersion,
-    pub fulu_fork_epoch: u64,
+    /// Unix timestamp of the beacon chain genesis in seconds
+    pub genesis_time: u64,
+    /// Forks ordered by the activation epoch
+    pub forks: Vec<ForkConfig>,
 }
 
 impl NetworkConfig {
     pub fn new(network: &Network) -> Self {
+        use ExecutionPayloadLayout::*;
+
         match network {
             Network::Mainnet => Self {
                 genesis_validators_root: [
@@ -68,16 +284,14 @@ impl NetworkConfig {
                     0x0f, 0xdd, 0x4e, 0x54, 0xbf, 0xe9, 0xf0, 0x6b, 0xf3, 0x3f, 0xf6, 0xcf, 0x5a,
                     0xd2, 0x7f, 0x51, 0x1b, 0xfe, 0x95,
                 ],
-                bellatrix_fork_version: [0x02, 0x00, 0x00, 0x00],
-                bellatrix_fork_epoch: 144896,
-                capella_fork_version: [0x03, 0x00, 0x00, 0x00],
-                capella_fork_epoch: 194048,
-                deneb_fork_version: [0x04, 0x00, 0x00, 0x00],
-                deneb_fork_epoch: 269568,
-                electra_fork_version: [0x05, 0x00, 0x00, 0x00],
-                electra_fork_epoch: 364032,
-                fulu_fork_version: [0x06, 0x00, 0x00, 0x00],
-                fulu_fork_epoch: 411392,
+                genesis_time: 1606824023,
+                forks: vec![
+                    ForkConfig::altair([0x02, 0x00, 0x00, 0x00], 144896, Bellatrix),
+                    ForkConfig::altair([0x03, 0x00, 0x00, 0x00], 194048, Capella),
+                    ForkConfig::altair([0x04, 0x00, 0x00, 0x00], 269568, Deneb),
+                    ForkConfig::electra([0x05, 0x00, 0x00, 0x00], 364032),
+                    ForkConfig::fulu([0x06, 0x00, 0x00, 0x00], 411392),
+                ],
             },
             Network::Goerli => Self {
                 genesis_validators_root: [
@@ -85,16 +299,13 @@ impl NetworkConfig {
                     0xd2, 0x37, 0x97, 0x75, 0x7d, 0x43, 0x09, 0x11, 0xa9, 0x32, 0x05, 0x30, 0xad,
                     0x8a, 0x0e, 0xab, 0xc4, 0x3e, 0xfb,
                 ],
-                bellatrix_fork_version: [0x02, 0x00, 0x10, 0x20],
-                bellatrix_fork_epoch: 112260,
-                capella_fork_version: [0x03, 0x00, 0x10, 0x20],
-                capella_fork_epoch: 162304,
-                deneb_fork_version: [0x04, 0x00, 0x10, 0x20],
-                deneb_fork_epoch: 231680,
-                electra_fork_version: [0x00, 0x00, 0x00, 0x00], // Not supported
-                electra_fork_epoch: u64::MAX,                   // Not supported
-                fulu_fork_version: [0x00, 0x00, 0x00, 0x00],    // Not supported
-                fulu_fork_epoch: u64::MAX,                      // Not supported
+                genesis_time: 1616508000,
+                // Electra and later forks are not supported
+                forks: vec![
+                    ForkConfig::altair([0x02, 0x00, 0x10, 0x20], 112260, Bellatrix),
+                    ForkConfig::altair([0x03, 0x00, 0x10, 0x20], 162304, Capella),
+                    ForkConfig::altair([0x04, 0x00, 0x10, 0x20], 231680, Deneb),
+                ],
             },
             Network::Sepolia => Self {
                 genesis_validators_root: [
@@ -102,96 +313,95 @@ impl NetworkConfig {
                     0x61, 0x05, 0x2a, 0xcf, 0x3f, 0x92, 0x09, 0xc0, 0x0e, 0x4e, 0xfb, 0xaa, 0xdd,
                     0xac, 0x09, 0xed, 0x9b, 0x80, 0x78,
                 ],
-                bellatrix_fork_version: [0x90, 0x00, 0x00, 0x71],
-                bellatrix_fork_epoch: 100,
-                capella_fork_version: [0x90, 0x00, 0x00, 0x72],
-                capella_fork_epoch: 56832,
-                deneb_fork_version: [0x90, 0x00, 0x00, 0x73],
-                deneb_fork_epoch: 132608,
-                electra_fork_version: [0x90, 0x00, 0x00, 0x74],
-                electra_fork_epoch: 222464,
-                fulu_fork_version: [0x90, 0x00, 0x00, 0x75],
-                fulu_fork_epoch: 272640,
+                genesis_time: 1655733600,
+                forks: vec![
+                    ForkConfig::altair([0x90, 0x00, 0x00, 0x71], 100, Bellatrix),
+                    ForkConfig::altair([0x90, 0x00, 0x00, 0x72], 56832, Capella),
+                    ForkConfig::altair([0x90, 0x00, 0x00, 0x73], 132608, Deneb),
+                    ForkConfig::electra([0x90, 0x00, 0x00, 0x74], 222464),
+                    ForkConfig::fulu([0x90, 0x00, 0x00, 0x75], 272640),
+                ],
             },
+            Network::Custom(config) => config.clone(),
         }
     }
 
-    pub fn compute_fork_version(&self, epoch: Epoch) -> Option<ForkVersion> {
-        if epoch >= self.fulu_fork_epoch {
-            return Some(self.fulu_fork_version);
+    /// Checks that the schedule is not empty and the forks are ordered by the activation epoch.
+    /// Several forks can be activated at the same epoch, e.g. at genesis of a devnet.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.forks.is_empty() {
+            return Err("The fork schedule is empty".to_string());
         }
 
-        if epoch >= self.electra_fork_epoch {
-            return Some(self.electra_fork_version);
+        for forks in self.forks.windows(2) {
+            if forks[1].epoch < forks[0].epoch {
+                return Err(format!(
+                    "The fork epochs should not decrease, but got {} after {}",
+                    forks[1].epoch, forks[0].epoch
+                ));
+            }
         }
 
-        if epoch >= self.deneb_fork_epoch {
-            return Some(self.deneb_fork_version);
-        }
+        Ok(())
+    }
 
-        if epoch >= self.capella_fork_epoch {
-            return Some(self.capella_fork_version);
+    /// Appends the fork to the end of the schedule, the schedule should stay valid
+    pub fn add_fork(&mut self, fork: ForkConfig) -> Result<(), String> {
+        self.forks.push(fork);
+        if let Err(err) = self.validate() {
+            self.forks.pop();
+            return Err(err);
         }
 
-        if epoch >= self.bellatrix_fork_epoch {
-            return Some(self.bellatrix_fork_version);
-        }
+        Ok(())
+    }
+
+    /// Returns the slot at the Unix timestamp in seconds, i.e. the current slot of the network
+    pub const fn compute_slot_at_timestamp(&self, timestamp: u64) -> Slot {
+        timestamp.saturating_sub(self.genesis_time) / SECONDS_PER_SLOT
+    }
+
+    pub fn get_fork(&self, epoch: Epoch) -> Option<&ForkConfig> {
+        self.forks.iter().rev().find(|fork| epoch >= fork.epoch)
+    }
 
-        None
+    pub fn get_fork_by_slot(&self, slot: Slot) -> Option<&ForkConfig> {
+        self.get_fork(compute_epoch_at_slot(slot))
+    }
+
+    pub fn compute_fork_version(&self, epoch: Epoch) -> Option<ForkVersion> {
+        self.get_fork(epoch).map(|fork| fork.version)
     }
 
     pub fn compute_fork_version_by_slot(&self, slot: Slot) -> Option<ForkVersion> {
         self.compute_fork_version(compute_epoch_at_slot(slot))
     }
 
-    pub const fn get_generalized_index_constants(&self, slot: Slot) -> GeneralizedIndex {
-        // Altair
-        pub const FINALIZED_ROOT_INDEX: u32 = 105;
-        pub const CURRENT_SYNC_COMMITTEE_INDEX: u32 = 55;
-        pub const NEXT_SYNC_COMMITTEE_INDEX: u32 = 55;
-
-        // Electra
-        pub const FINALIZED_ROOT_INDEX_ELECTRA: u32 = 169;
-        pub const CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA: u32 = 86;
-        pub const NEXT_SYNC_COMMITTEE_INDEX_ELECTRA: u32 = 87;
-
-        // Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/capella/light-client/sync-protocol.md
-        pub const EXECUTION_PAYLOAD_GINDEX: u32 = 25;
-
-        let epoch = compute_epoch_at_slot(slot);
-
-        if epoch >= self.electra_fork_epoch {
-            GeneralizedIndex {
-                finality_tree_depth: floorlog2(FINALIZED_ROOT_INDEX_ELECTRA),
-                finality_tree_index: get_subtree_index(FINALIZED_ROOT_INDEX_ELECTRA),
-                current_sync_committee_tree_depth: floorlog2(CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA),
-                current_sync_committee_tree_index: get_subtree_index(
-                    CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA,
-                ),
-                sync_committee_tree_depth: floorlog2(NEXT_SYNC_COMMITTEE_INDEX_ELECTRA),
-                sync_committee_tree_index: get_subtree_index(NEXT_SYNC_COMMITTEE_INDEX_ELECTRA),
-                execution_payload_tree_depth: floorlog2(EXECUTION_PAYLOAD_GINDEX),
-                execution_payload_tree_index: get_subtree_index(EXECUTION_PAYLOAD_GINDEX),
-            }
-        } else {
-            GeneralizedIndex {
-                finality_tree_depth: floorlog2(FINALIZED_ROOT_INDEX),
-                finality_tree_index: get_subtree_index(FINALIZED_ROOT_INDEX),
-                current_sync_committee_tree_depth: floorlog2(CURRENT_SYNC_COMMITTEE_INDEX),
-                current_sync_committee_tree_index: get_subtree_index(CURRENT_SYNC_COMMITTEE_INDEX),
-                sync_committee_tree_depth: floorlog2(NEXT_SYNC_COMMITTEE_INDEX),
-                sync_committee_tree_index: get_subtree_index(NEXT_SYNC_COMMITTEE_INDEX),
-                execution_payload_tree_depth: floorlog2(EXECUTION_PAYLOAD_GINDEX),
-                execution_payload_tree_index: get_subtree_index(EXECUTION_PAYLOAD_GINDEX),
-            }
-        }
+    pub fn get_generalized_index_constants(&self, slot: Slot) -> Option<GeneralizedIndex> {
+        let fork = self.get_fork_by_slot(slot)?;
+
+        Some(GeneralizedIndex {
+            finality_tree_depth: floorlog2(fork.finalized_root_gindex),
+            finality_tree_index: get_subtree_index(fork.finalized_root_gindex),
+            current_sync_committee_tree_depth: floorlog2(fork.current_sync_committee_gindex),
+            current_sync_committee_tree_index: get_subtree_index(
+                fork.current_sync_committee_gindex,
+            ),
+            sync_committee_tree_depth: floorlog2(fork.next_sync_committee_gindex),
+            sync_committee_tree_index: get_subtree_index(fork.next_sync_committee_gindex),
+            execution_payload_tree_depth: floorlog2(fork.execution_payload_gindex),
+            execution_payload_tree_index: get_subtree_index(fork.execution_payload_gindex),
+        })
     }
 
     // Fork-aware execution root computation - manual tree hashing per fork
-    pub fn get_lc_execution_root(&self, header: &LightClientHeader) -> H256 {
+    pub fn get_lc_execution_root(
+        &self,
+        header: &LightClientHeader,
+        execution_payload_layout: ExecutionPayloadLayout,
+    ) -> H256 {
         use tree_hash::{MerkleHasher, TreeHash};
 
-        let epoch = compute_epoch_at_slot(header.beacon.slot);
         let execution = &header.execution;
 
         let mut leaves: Vec<tree_hash::Hash256> = vec![
@@ -212,7 +422,7 @@ impl NetworkConfig {
         ];
 
         // Add withdrawals for Capella+
-        if epoch >= self.capella_fork_epoch {
+        if execution_payload_layout >= ExecutionPayloadLayout::Capella {
             leaves.push(
                 execution
                     .withdrawals_root
@@ -222,7 +432,7 @@ impl NetworkConfig {
         }
 
         // Add blob fields for Deneb+
-        if epoch >= self.deneb_fork_epoch {
+        if execution_payload_layout >= ExecutionPayloadLayout::Deneb {
             leaves.push(execution.blob_gas_used.unwrap_or_default().tree_hash_root());
             leaves.push(
                 execution
@@ -243,25 +453,104 @@ impl NetworkConfig {
         H256(hasher.finish().unwrap().0.into())
     }
 
+    /// First slot of the first fork with the `historical_summaries` in the beacon state
+    pub fn get_capella_slot(&self) -> Option<Slot> {
+        self.forks
+            .iter()
+            .find(|fork| fork.execution_payload_layout >= ExecutionPayloadLayout::Capella)
+            .map(|fork| fork.epoch * SLOTS_PER_EPOCH)
+    }
+
+    /// Verifies that the beacon block of `proof.header` is included in the `block_roots` or
+    /// the `historical_summaries` of the beacon state with the given root, and that the
+    /// execution payload of the header is valid. Blocks before Capella are not supported.
+    pub fn is_valid_historical_block_proof(
+        &self,
+        proof: &HistoricalBlockProof,
+        state_root: H256,
+    ) -> bool {
+        let block_slot = proof.header.beacon.slot;
+        let capella_slot = match self.get_capella_slot() {
+            Some(capella_slot) => capella_slot,
+            None => return false,
+        };
+        if block_slot < capella_slot || block_slot >= proof.state_slot {
+            return false;
+        }
+
+        let state_fork = match self.get_fork_by_slot(proof.state_slot) {
+            Some(fork) => fork,
+            None => return false,
+        };
+        let state_tree_depth = state_fork.beacon_state_tree_depth() as usize;
+
+        if proof.block_root_branch.len() != BLOCK_ROOTS_TREE_DEPTH
+            || !self.is_valid_light_client_header(&proof.header)
+        {
+            return false;
+        }
+
+        // Root of the `block_roots` vector, either of the state or of the historical summary
+        let block_roots_root = merkle_root_from_branch(
+            H256(proof.header.beacon.tree_hash_root().0.into()),
+            &proof.block_root_branch,
+            BLOCK_ROOTS_TREE_DEPTH,
+            (block_slot % SLOTS_PER_HISTORICAL_ROOT) as usize,
+        );
+
+        if proof.state_slot - block_slot <= SLOTS_PER_HISTORICAL_ROOT {
+            verify_merkle_proof(
+                block_roots_root,
+                &proof.block_roots_branch,
+                state_tree_depth,
+                BLOCK_ROOTS_FIELD_INDEX as usize,
+                state_root,
+            )
+        } else {
+            // `block_summary_root` is the first field of the `HistoricalSummary`, and the list
+            // items are the left subtree of the list root mixed in with the length
+            let summary_index = (block_slot / SLOTS_PER_HISTORICAL_ROOT
+                - capella_slot / SLOTS_PER_HISTORICAL_ROOT)
+                as usize;
+            let depth = state_tree_depth + 1 + HISTORICAL_SUMMARIES_TREE_DEPTH + 1;
+            let index = ((HISTORICAL_SUMMARIES_FIELD_INDEX as usize)
+                << (HISTORICAL_SUMMARIES_TREE_DEPTH + 2))
+                | (summary_index << 1);
+            verify_merkle_proof(
+                block_roots_root,
+                &proof.block_roots_branch,
+                depth,
+                index,
+                state_root,
+            )
+        }
+    }
+
     pub fn is_valid_light_client_header(&self, header: &LightClientHeader) -> bool {
-        let epoch = compute_epoch_at_slot(header.beacon.slot);
-        if epoch < self.deneb_fork_epoch {
-            if header.execution.blob_gas_used.is_some()
-                || header.execution.excess_blob_gas.is_some()
-            {
-                return false;
-            }
+        let fork = match self.get_fork_by_slot(header.beacon.slot) {
+            Some(fork) => fork,
+            None => return false,
+        };
+
+        if fork.execution_payload_layout < ExecutionPayloadLayout::Deneb
+            && (header.execution.blob_gas_used.is_some()
+                || header.execution.excess_blob_gas.is_some())
+        {
+            return false;
         }
 
-        if epoch < self.capella_fork_epoch {
+        if fork.execution_payload_layout < ExecutionPayloadLayout::Capella {
             panic!("Unsupported fork");
         }
 
         // Use fork-aware proof parameters
-        let generalized_index = self.get_generalized_index_constants(epoch);
+        let generalized_index = match self.get_generalized_index_constants(header.beacon.slot) {
+            Some(generalized_index) => generalized_index,
+            None => return false,
+        };
 
         verify_merkle_proof(
-            self.get_lc_execution_root(header),
+            self.get_lc_execution_root(header, fork.execution_payload_layout),
             &header.execution_branch,
             generalized_index
                 .execution_payload_tree_depth
@@ -362,13 +651,59 @@ pub fn verify_merkle_proof(
     }
 }
 
+/// Generalized index of the field of the validator, given the generalized index of the
+/// `validators` list, e.g. `withdrawal_credentials` or `effective_balance`
+pub fn get_validator_field_gindex(
+    validators_gindex: u64,
+    validator_index: u64,
+    field: &str,
+) -> Option<u64> {
+    let field_index = VALIDATOR_FIELDS.iter().position(|name| *name == field)? as u64;
+    if validator_index >= 1 << VALIDATOR_REGISTRY_LIMIT_DEPTH {
+        return None;
+    }
+
+    // The list items are the left subtree of the list root mixed in with the length
+    let validator_gindex = validators_gindex
+        .checked_mul(2 << VALIDATOR_REGISTRY_LIMIT_DEPTH)?
+        .checked_add(validator_index)?;
+    validator_gindex.checked_mul(8)?.checked_add(field_index)
+}
+
+/// Generalized index of the chunk of the `balances` list with the balance of the validator,
+/// given the generalized index of the `balances` list. Each chunk packs 4 little-endian balances.
+pub fn get_balance_chunk_gindex(balances_gindex: u64, validator_index: u64) -> Option<u64> {
+    if validator_index >= 1 << VALIDATOR_REGISTRY_LIMIT_DEPTH {
+        return None;
+    }
+
+    balances_gindex
+        .checked_mul(2 << (VALIDATOR_REGISTRY_LIMIT_DEPTH - 2))?
+        .checked_add(validator_index / 4)
+}
+
 /// Compute a root hash from a leaf and a Merkle proof.
 pub fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index: usize) -> H256 {
     assert_eq!(branch.len(), depth, "proof length should equal depth");
+    compute_merkle_root(leaf, branch, index as u64)
+}
+
+/// Verify a Merkle proof of the leaf at the generalized index. Unlike `verify_merkle_proof`
+/// supports the trees deeper than 32 levels, e.g. the validators of the beacon state.
+pub fn verify_merkle_proof_by_gindex(leaf: H256, branch: &[H256], gindex: u64, root: H256) -> bool {
+    if gindex == 0 || branch.len() != (63 - gindex.leading_zeros()) as usize {
+        return false;
+    }
+
+    compute_merkle_root(leaf, branch, gindex) == root
+}
 
+/// Compute a root hash from a leaf and a Merkle proof, only the lowest `branch.len()` bits of
+/// the index are used.
+fn compute_merkle_root(leaf: H256, branch: &[H256], index: u64) -> H256 {
     let mut merkle_root = leaf.0.as_bytes().to_vec();
 
-    for (i, leaf) in branch.iter().enumerate().take(depth) {
+    for (i, leaf) in branch.iter().enumerate() {
         let ith_bit = (index >> i) & 0x01;
         if ith_bit == 1 {
             merkle_root =
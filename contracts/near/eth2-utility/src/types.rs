@@ -2,7 +2,7 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use eth_types::eth2::*;
 use eth_types::H256;
 use near_sdk::near;
-use near_sdk::AccountId;
+use near_sdk::{AccountId, Gas, NearToken};
 
 use crate::consensus::Network;
 
@@ -15,6 +15,65 @@ pub struct ExecutionHeaderInfo {
     pub submitter: AccountId,
 }
 
+/// Roots of the execution block tries, stored to verify proofs without the full header.
+#[derive(Clone, Debug, PartialEq)]
+#[near(serializers=[borsh])]
+pub struct ExecutionBlockRoots {
+    pub receipts_root: H256,
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub timestamp: u64,
+}
+
+impl From<&eth_types::BlockHeader> for ExecutionBlockRoots {
+    fn from(header: &eth_types::BlockHeader) -> Self {
+        Self {
+            receipts_root: header.receipts_root,
+            state_root: header.state_root,
+            transactions_root: header.transactions_root,
+            timestamp: header.timestamp,
+        }
+    }
+}
+
+/// Public keys of the sync committee decompressed to the uncompressed G1 points, cached to avoid
+/// the decompression on each signature verification
+#[derive(Clone)]
+#[near(serializers=[borsh])]
+pub struct DecompressedSyncCommittee {
+    /// Concatenated 96 bytes points of the committee members
+    pub pubkeys: Vec<u8>,
+    pub aggregate_pubkey: Vec<u8>,
+}
+
+/// Proof of the beacon block finalized before the beacon state stored by the client.
+/// Allows to verify the execution blocks that fell out of the `hashes_gc_threshold` window.
+#[derive(Clone)]
+#[near(serializers=[borsh])]
+pub struct HistoricalBlockProof {
+    /// Slot of the finalized beacon block whose state root is stored by the client
+    pub state_slot: u64,
+    /// Header of the proven beacon block with the execution payload branch
+    pub header: LightClientHeader,
+    /// Branch from the beacon block root to the root of the `block_roots` vector
+    pub block_root_branch: Vec<H256>,
+    /// Branch from the root of the `block_roots` vector to the state root. Proves the `block_roots`
+    /// field if the block is within `SLOTS_PER_HISTORICAL_ROOT` slots before the state, otherwise
+    /// proves the `block_summary_root` of the `historical_summaries` item of the block.
+    pub block_roots_branch: Vec<H256>,
+}
+
+/// Two validly signed updates finalizing different headers at the same slot
+#[derive(Clone)]
+#[near(serializers=[borsh])]
+pub struct ConflictingUpdatesEvidence {
+    pub first_update: LightClientUpdate,
+    pub second_update: LightClientUpdate,
+    pub reporter: AccountId,
+    /// NEAR block height of the report
+    pub reported_at: u64,
+}
+
 #[derive(Clone)]
 #[near(serializers=[borsh])]
 pub struct InitInput {
@@ -29,7 +88,25 @@ pub struct InitInput {
     pub trusted_signer: Option<AccountId>,
 }
 
+/// Input for the initialization from the `LightClientBootstrap` of a trusted checkpoint
+#[derive(Clone)]
+#[near(serializers=[borsh])]
+pub struct BootstrapInitInput {
+    pub network: Network,
+    /// Root of the trusted beacon block, e.g. the weak subjectivity checkpoint root
+    pub trusted_block_root: H256,
+    pub bootstrap: LightClientBootstrap,
+    /// Update attested and signed within the bootstrap period, which proves the next sync committee
+    pub update: LightClientUpdate,
+    /// Execution block of the bootstrap header
+    pub finalized_execution_header: eth_types::BlockHeader,
+    pub validate_updates: bool,
+    pub hashes_gc_threshold: u64,
+    pub trusted_signer: Option<AccountId>,
+}
+
 #[derive(Clone, BorshDeserialize, BorshSerialize, PartialEq, BorshSchema, Debug)]
+#[near(serializers=[json])]
 pub enum ClientMode {
     SubmitLightClientUpdate,
     SubmitHeader,
@@ -44,4 +121,45 @@ pub struct ContractConfig {
     pub hashes_gc_threshold: u64,
     pub network: Network,
     pub trusted_blocks_submitter: Option<AccountId>,
+    pub store_execution_roots: bool,
+    pub max_gc_entries_per_call: u64,
+    pub force_update_timeout: Option<u64>,
+    pub light_client_update_reward: Option<NearToken>,
+}
+
+/// Submissions of a relayer account accepted by the client
+#[derive(Clone, Default, Debug, PartialEq)]
+#[near(serializers=[borsh, json])]
+pub struct SubmitterStats {
+    pub light_client_updates: u64,
+    pub execution_headers: u64,
+    /// Gas burnt by the calls with the accepted submissions
+    pub gas_burnt: Gas,
+    /// Rewards for the accepted light client updates which are not claimed yet
+    pub unclaimed_rewards: NearToken,
+    pub claimed_rewards: NearToken,
+}
+
+/// Snapshot of the client synchronization state
+#[derive(Clone)]
+#[near(serializers=[json])]
+pub struct SyncStatus {
+    pub finalized_beacon_slot: u64,
+    pub finalized_period: u64,
+    pub finalized_execution_block_number: u64,
+    pub unfinalized_head_block_number: Option<u64>,
+    pub unfinalized_tail_block_number: Option<u64>,
+    pub client_mode: ClientMode,
+    /// All the execution blocks below this number are garbage collected
+    pub gc_lower_bound: u64,
+    /// The execution blocks from this number to the finalized one are within the
+    /// `hashes_gc_threshold` window and aren't scheduled for the gc
+    pub oldest_provable_block_number: u64,
+    /// Whether the next sync committee is stored
+    pub next_sync_committee_present: bool,
+    /// Paused methods
+    pub paused: Vec<String>,
+    /// Whether the submissions are halted by the reported conflicting updates
+    pub submissions_halted: bool,
+    pub config: ContractConfig,
 }
@@ -1,11 +1,15 @@
 use crate::constants::protocol::{EPOCHS_PER_PERIOD, SLOTS_PER_EPOCH};
 use color_eyre::{Result, eyre::Context};
-use eth_types::eth2::LightClientUpdate as BorshLightClientUpdate;
+use eth_types::eth2::{
+    LightClientBootstrap as BorshLightClientBootstrap, LightClientUpdate as BorshLightClientUpdate,
+};
 use eth2::{BeaconNodeHttpClient, Timeouts};
 use sensitive_url::SensitiveUrl;
+use std::str::FromStr;
 use std::time::Duration;
 use types::{
-    BeaconResponse, ExecPayload, FullPayloadRef, LightClientFinalityUpdate,
+    BeaconResponse, ExecPayload, FullPayloadRef, Hash256,
+    LightClientBootstrap as LighthouseLightClientBootstrap, LightClientFinalityUpdate,
     LightClientUpdate as LighthouseLightClientUpdate, MainnetEthSpec, Slot,
 };
 
@@ -31,31 +35,45 @@ impl BeaconClient {
 
     /// Fetch light client update for a specific period using Lighthouse's client
     pub async fn fetch_period_update(&self, period: u64) -> Result<BorshLightClientUpdate> {
+        self.fetch_period_updates(period, 1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!("No light client update found for period {}", period)
+            })
+    }
+
+    /// Fetch light client updates for `count` consecutive periods starting from `start_period`
+    pub async fn fetch_period_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<BorshLightClientUpdate>> {
         let updates: Vec<BeaconResponse<LighthouseLightClientUpdate<MainnetEthSpec>>> = self
             .client
-            .get_beacon_light_client_updates(period, 1)
+            .get_beacon_light_client_updates(start_period, count)
             .await
             .map_err(|e| {
                 color_eyre::eyre::eyre!(
                     "Failed to fetch light client updates for period {}: {:?}",
-                    period,
+                    start_period,
                     e
                 )
             })?
             .ok_or_else(|| {
-                color_eyre::eyre::eyre!("No light client updates found for period {}", period)
+                color_eyre::eyre::eyre!("No light client updates found for period {}", start_period)
             })?;
 
-        let update = updates.into_iter().next().ok_or_else(|| {
-            color_eyre::eyre::eyre!("No light client update found for period {}", period)
-        })?;
-
-        let v = serde_json::to_value(update.data())
-            .wrap_err("Failed to serialize light client update to JSON")?;
-        let custom_update: BorshLightClientUpdate = serde_json::from_value(v)
-            .wrap_err("Failed to deserialize light client update from JSON")?;
-
-        Ok(custom_update)
+        updates
+            .iter()
+            .map(|update| {
+                let v = serde_json::to_value(update.data())
+                    .wrap_err("Failed to serialize light client update to JSON")?;
+                serde_json::from_value(v)
+                    .wrap_err("Failed to deserialize light client update from JSON")
+            })
+            .collect()
     }
 
     /// Fetch latest finality update
@@ -81,6 +99,37 @@ impl BeaconClient {
         Ok(custom_update)
     }
 
+    /// Fetch light client bootstrap for a trusted block root
+    pub async fn fetch_bootstrap(&self, block_root: &str) -> Result<BorshLightClientBootstrap> {
+        let root = Hash256::from_str(block_root)
+            .map_err(|e| color_eyre::eyre::eyre!("Invalid block root '{}': {:?}", block_root, e))?;
+
+        let bootstrap: BeaconResponse<LighthouseLightClientBootstrap<MainnetEthSpec>> = self
+            .client
+            .get_light_client_bootstrap(root)
+            .await
+            .map_err(|e| {
+                color_eyre::eyre::eyre!(
+                    "Failed to fetch light client bootstrap for block root {}: {:?}",
+                    block_root,
+                    e
+                )
+            })?
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "No light client bootstrap found for block root {}",
+                    block_root
+                )
+            })?;
+
+        let v = serde_json::to_value(bootstrap.data())
+            .wrap_err("Failed to serialize light client bootstrap to JSON")?;
+        let custom_bootstrap: BorshLightClientBootstrap = serde_json::from_value(v)
+            .wrap_err("Failed to deserialize light client bootstrap from JSON")?;
+
+        Ok(custom_bootstrap)
+    }
+
     /// Get the last finalized slot
     pub async fn get_last_finalized_slot(&self) -> Result<u64> {
         let finality_checkpoints = self
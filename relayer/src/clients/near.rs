@@ -4,7 +4,7 @@ use eth_types::{
     BlockHeader, H256,
     eth2::{LightClientState, LightClientUpdate},
 };
-use eth2_utility::types::{ClientMode, InitInput};
+use eth2_utility::types::{BootstrapInitInput, ClientMode, InitInput};
 use indicatif::{ProgressBar, ProgressState, ProgressStyle};
 use near_crypto::Signer;
 use near_fetch::ops::{Function, Transaction};
@@ -195,7 +195,48 @@ impl ContractClient {
         Ok(())
     }
 
-    /// Submit multiple execution headers in a single batch transaction
+    /// Submit light client updates of consecutive periods in a single transaction
+    pub async fn submit_light_client_updates(&self, updates: Vec<LightClientUpdate>) -> Result<()> {
+        let updates_count = updates.len();
+        timeout(
+            Duration::from_secs(self.timeout_secs),
+            self.client
+                .call(
+                    &self.signer,
+                    &self.eth_light_client_account_id,
+                    "submit_beacon_chain_light_client_updates",
+                )
+                .args_borsh(updates)
+                .gas(MAX_GAS)
+                .retry_exponential(1000, 3)
+                .transact(),
+        )
+        .await
+        .map_err(|e| {
+            error!("Light client updates submission timed out: {:#}", e);
+            e
+        })
+        .wrap_err("NEAR call timed out")?
+        .map_err(|e| {
+            error!("Light client updates transaction failed: {:#}", e);
+            e
+        })
+        .wrap_err("Failed to send light client updates transaction")?
+        .into_result()
+        .map_err(|e| {
+            error!("Light client updates rejected by contract: {:#}", e);
+            e
+        })
+        .wrap_err("Failed to submit light client updates")?;
+
+        info!(
+            "{} light client updates submitted successfully",
+            updates_count
+        );
+        Ok(())
+    }
+
+    /// Submit multiple execution headers, one `submit_execution_headers` call per batch
     pub async fn submit_execution_headers(&self, headers: &[BlockHeader]) -> Result<()> {
         if headers.is_empty() {
             info!("No headers to submit");
@@ -226,18 +267,14 @@ impl ContractClient {
         };
 
         for (batch_index, header_batch) in batched_headers.iter().enumerate() {
-            let attached_gas_per_promise_in_batch = MAX_GAS.as_gas() / header_batch.len() as u64;
-
-            let mut batch = self
+            // The whole batch is verified by the contract in a single function call
+            let function = Function::new("submit_execution_headers")
+                .args_borsh(header_batch.to_vec())
+                .gas(MAX_GAS);
+            let batch = self
                 .client
-                .batch(&self.signer, &self.eth_light_client_account_id);
-
-            for header in *header_batch {
-                let function = Function::new("submit_execution_header")
-                    .args_borsh(header.clone())
-                    .gas(NearGas::from_gas(attached_gas_per_promise_in_batch));
-                batch = batch.call(function);
-            }
+                .batch(&self.signer, &self.eth_light_client_account_id)
+                .call(function);
 
             if self.relayer_config.fast_mode {
                 self.submit_batch_async(batch, batch_index, total_batches)
@@ -371,6 +408,34 @@ impl ContractClient {
         Ok(())
     }
 
+    /// Initialize the contract from the `LightClientBootstrap` of a trusted checkpoint
+    pub async fn init_contract_from_bootstrap(&self, init_input: BootstrapInitInput) -> Result<()> {
+        self.client
+            .call(
+                &self.signer,
+                &self.eth_light_client_account_id,
+                "init_from_bootstrap",
+            )
+            .args_borsh(init_input)
+            .gas(MAX_GAS)
+            .transact()
+            .await
+            .map_err(|e| {
+                error!("Contract initialization transaction failed: {:#}", e);
+                e
+            })
+            .wrap_err("Failed to send contract initialization transaction")?
+            .into_result()
+            .map_err(|e| {
+                error!("Contract initialization rejected: {:#}", e);
+                e
+            })
+            .wrap_err("Contract initialization failed")?;
+
+        info!("Contract initialized from bootstrap successfully");
+        Ok(())
+    }
+
     /// Get contract account ID
     pub fn eth_light_client_account_id(&self) -> &AccountId {
         &self.eth_light_client_account_id
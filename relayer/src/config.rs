@@ -9,6 +9,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use crate::constants::defaults;
+use crate::constants::protocol::{EPOCHS_PER_PERIOD, SLOTS_PER_EPOCH};
 
 /// Target network for configuration
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -16,6 +17,7 @@ pub enum Network {
     #[default]
     Testnet,
     Mainnet,
+    Custom,
 }
 
 impl FromStr for Network {
@@ -25,8 +27,9 @@ impl FromStr for Network {
         match s.to_lowercase().as_str() {
             "testnet" | "sepolia" => Ok(Network::Testnet),
             "mainnet" => Ok(Network::Mainnet),
+            "custom" | "devnet" => Ok(Network::Custom),
             _ => Err(format!(
-                "Unknown network: {}. Use 'testnet' or 'mainnet'",
+                "Unknown network: {}. Use 'testnet', 'mainnet' or 'custom'",
                 s
             )),
         }
@@ -112,6 +115,11 @@ pub struct RelayerConfig {
     #[serde(default)]
     pub max_headers_per_loop: usize,
 
+    /// Maximum number of light client updates submitted in one transaction
+    /// when the client is several sync committee periods behind
+    #[serde(default)]
+    pub max_updates_per_batch: usize,
+
     /// Sleep duration when synced (seconds)
     #[serde(default)]
     pub sync_sleep_secs: u64,
@@ -152,6 +160,13 @@ pub struct InitConfig {
     /// Hashes threshold for Garbage Collection
     #[serde(default)]
     pub hashes_gc_threshold: u64,
+
+    /// Path to the file with the genesis validators root and fork schedule,
+    /// required for the `custom` network
+    pub network_config: Option<String>,
+
+    /// Trusted checkpoint block root, required for the initialization from bootstrap
+    pub trusted_block_root: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,6 +218,7 @@ impl Default for RelayerConfig {
             update_interval_epochs: defaults::UPDATE_INTERVAL_EPOCHS,
             headers_batch_size: defaults::HEADERS_BATCH_SIZE,
             max_headers_per_loop: defaults::MAX_HEADERS_PER_LOOP,
+            max_updates_per_batch: defaults::MAX_UPDATES_PER_BATCH,
             sync_sleep_secs: defaults::SYNC_SLEEP_SECS,
             submission_sleep_secs: defaults::SUBMISSION_SLEEP_SECS,
             max_iterations: None,
@@ -220,7 +236,31 @@ impl Default for InitConfig {
             first_update: "".to_string(),
             headers: "".to_string(),
             hashes_gc_threshold: 51000,
+            network_config: None,
+            trusted_block_root: None,
+        }
+    }
+}
+
+impl InitConfig {
+    /// Ethereum network the EthLightClient contract is initialized for
+    pub fn ethereum_network(&self) -> Result<eth2_utility::consensus::Network> {
+        if Network::from_str(&self.network) == Ok(Network::Custom) {
+            let path = self.network_config.as_ref().ok_or_else(|| {
+                color_eyre::eyre::eyre!("network_config must be provided for the custom network")
+            })?;
+            let network_config: eth2_utility::consensus::NetworkConfig =
+                serde_json::from_reader(std::fs::File::open(path)?)
+                    .wrap_err_with(|| format!("Failed to parse network config '{path}'"))?;
+            network_config
+                .validate()
+                .map_err(|err| color_eyre::eyre::eyre!("Invalid network config: {err}"))?;
+
+            return Ok(eth2_utility::consensus::Network::Custom(network_config));
         }
+
+        eth2_utility::consensus::Network::from_str(&self.network)
+            .map_err(|err| color_eyre::eyre::eyre!("Incorrect network name: {err:?}"))
     }
 }
 
@@ -331,6 +371,25 @@ impl Config {
             ));
         }
 
+        if self.relayer.max_updates_per_batch == 0 {
+            return Err(color_eyre::eyre::eyre!(
+                "max_updates_per_batch must be greater than 0"
+            ));
+        }
+
+        // The batch finalizes up to a period of blocks per update on top of the period of the
+        // client, the contract rejects the batches moving further than `hashes_gc_threshold`
+        let max_batch_blocks =
+            (self.relayer.max_updates_per_batch as u64 + 1) * SLOTS_PER_EPOCH * EPOCHS_PER_PERIOD;
+        if max_batch_blocks > self.init.hashes_gc_threshold {
+            return Err(color_eyre::eyre::eyre!(
+                "max_updates_per_batch {} may finalize {} blocks in one batch, which exceeds hashes_gc_threshold {}",
+                self.relayer.max_updates_per_batch,
+                max_batch_blocks,
+                self.init.hashes_gc_threshold
+            ));
+        }
+
         Ok(())
     }
 
@@ -406,6 +465,36 @@ impl Config {
                     first_update: "".to_string(),
                     headers: "".to_string(),
                     hashes_gc_threshold: 51000,
+                    network_config: None,
+                    trusted_block_root: None,
+                },
+                logging: LoggingConfig::default(),
+            },
+            Network::Custom => Config {
+                beacon: BeaconConfig {
+                    endpoint: defaults::CUSTOM_BEACON_ENDPOINT.to_string(),
+                },
+                execution: ExecutionConfig {
+                    endpoint: defaults::CUSTOM_EXECUTION_ENDPOINT.to_string(),
+                    max_batch_size: defaults::EXECUTION_BATCH_SIZE,
+                },
+                near: NearConfig {
+                    endpoint: defaults::CUSTOM_NEAR_ENDPOINT.to_string(),
+                    eth_light_client_account_id: defaults::CUSTOM_ETH_LIGHT_CLIENT_ACCOUNT_ID
+                        .to_string(),
+                    signer_account_id: defaults::CUSTOM_SIGNER_ACCOUNT_ID.to_string(),
+                    secret_key: String::new(),
+                    timeout_secs: defaults::TIMEOUT_SECS,
+                },
+                relayer: RelayerConfig::default(),
+                init: InitConfig {
+                    network: "custom".to_string(),
+                    init_update: "".to_string(),
+                    first_update: "".to_string(),
+                    headers: "".to_string(),
+                    hashes_gc_threshold: 51000,
+                    network_config: Some(defaults::CUSTOM_NETWORK_CONFIG_PATH.to_string()),
+                    trusted_block_root: None,
                 },
                 logging: LoggingConfig::default(),
             },
@@ -435,6 +524,18 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_max_updates_per_batch_validation() {
+        let mut config = Config::default();
+        config.near.eth_light_client_account_id = "client.testnet".to_string();
+        config.near.signer_account_id = "relayer.testnet".to_string();
+        config.near.secret_key = "ed25519:secret".to_string();
+        assert!(config.validate().is_ok());
+
+        config.relayer.max_updates_per_batch = 6;
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_example_toml_generation() {
         let toml = Config::example_toml().unwrap();
@@ -444,4 +545,41 @@ mod tests {
         assert!(toml.contains("[init]"));
         assert!(toml.contains("[logging]"));
     }
+
+    #[test]
+    fn test_custom_network_config() {
+        let config = Config::for_network(Network::Custom);
+        assert_eq!(config.init.network, "custom");
+        assert!(
+            Config::example_toml_for_network(Network::Custom)
+                .unwrap()
+                .contains("network_config")
+        );
+
+        let init = InitConfig {
+            network_config: Some("network_configs/hoodi.json".to_string()),
+            ..config.init
+        };
+        let eth2_utility::consensus::Network::Custom(network_config) =
+            init.ethereum_network().unwrap()
+        else {
+            panic!("Expected the custom network");
+        };
+        assert_eq!(network_config.forks.len(), 5);
+
+        let devnet_init = InitConfig {
+            network: "devnet".to_string(),
+            ..init.clone()
+        };
+        assert!(matches!(
+            devnet_init.ethereum_network().unwrap(),
+            eth2_utility::consensus::Network::Custom(_)
+        ));
+
+        let init = InitConfig {
+            network_config: None,
+            ..init
+        };
+        assert!(init.ethereum_network().is_err());
+    }
 }
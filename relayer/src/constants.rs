@@ -23,6 +23,14 @@ pub mod defaults {
     pub const MAINNET_ETH_LIGHT_CLIENT_ACCOUNT_ID: &str = "client-eth2.near";
     pub const MAINNET_SIGNER_ACCOUNT_ID: &str = "relayer.near";
 
+    // Custom network endpoints (e.g. a local Ethereum devnet + NEAR localnet)
+    pub const CUSTOM_BEACON_ENDPOINT: &str = "http://localhost:5052";
+    pub const CUSTOM_EXECUTION_ENDPOINT: &str = "http://localhost:8545";
+    pub const CUSTOM_NEAR_ENDPOINT: &str = "http://localhost:3030";
+    pub const CUSTOM_ETH_LIGHT_CLIENT_ACCOUNT_ID: &str = "client-eth2.test.near";
+    pub const CUSTOM_SIGNER_ACCOUNT_ID: &str = "test.near";
+    pub const CUSTOM_NETWORK_CONFIG_PATH: &str = "./network_config.json";
+
     // Legacy aliases (default to testnet)
     pub const BEACON_ENDPOINT: &str = TESTNET_BEACON_ENDPOINT;
     pub const EXECUTION_ENDPOINT: &str = TESTNET_EXECUTION_ENDPOINT;
@@ -41,6 +49,7 @@ pub mod defaults {
     pub const UPDATE_INTERVAL_EPOCHS: u64 = 1;
     pub const HEADERS_BATCH_SIZE: usize = 100;
     pub const MAX_HEADERS_PER_LOOP: usize = 1000;
+    pub const MAX_UPDATES_PER_BATCH: usize = 4;
     pub const SYNC_SLEEP_SECS: u64 = 60;
     pub const SUBMISSION_SLEEP_SECS: u64 = 12;
 
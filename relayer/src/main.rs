@@ -18,6 +18,8 @@ enum NetworkArg {
     Testnet,
     /// Ethereum mainnet + NEAR mainnet
     Mainnet,
+    /// Custom Ethereum network (e.g. Holesky, Hoodi or a local devnet) + NEAR localnet
+    Custom,
 }
 
 impl From<NetworkArg> for Network {
@@ -25,6 +27,7 @@ impl From<NetworkArg> for Network {
         match arg {
             NetworkArg::Testnet => Network::Testnet,
             NetworkArg::Mainnet => Network::Mainnet,
+            NetworkArg::Custom => Network::Custom,
         }
     }
 }
@@ -61,6 +64,8 @@ enum Commands {
     RunJob,
     /// Init Eth Light Client contract
     Init,
+    /// Init Eth Light Client contract from the light client bootstrap of a trusted checkpoint
+    InitFromBootstrap,
 }
 
 fn setup_logging(level: &str, json: bool) -> Result<()> {
@@ -130,6 +135,17 @@ async fn handle_command(command: Commands, config_path: Option<PathBuf>) -> Resu
 
             EthRelayer::new(config).await?.init_eth_client().await?;
         }
+        Commands::InitFromBootstrap => {
+            let config = Config::load(config_path)?;
+            config.validate()?;
+            setup_logging(&config.logging.level, config.logging.json)?;
+            config.print_summary();
+
+            EthRelayer::new(config)
+                .await?
+                .init_eth_client_from_bootstrap()
+                .await?;
+        }
     }
     Ok(())
 }
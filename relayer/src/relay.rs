@@ -1,4 +1,3 @@
-use std::str::FromStr;
 use std::time::Duration;
 
 use crate::constants::protocol::SLOTS_PER_EPOCH;
@@ -6,7 +5,7 @@ use crate::{BeaconClient, ContractClient, ExecutionClient};
 use color_eyre::Result;
 use eth_types::BlockHeader;
 use eth_types::eth2::LightClientUpdate;
-use eth2_utility::types::{ClientMode, InitInput};
+use eth2_utility::types::{BootstrapInitInput, ClientMode, InitInput};
 use near_crypto::{InMemorySigner, SecretKey};
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
@@ -137,8 +136,7 @@ impl EthRelayer {
             serde_json::from_reader(std::fs::File::open(&self.config.init.headers)?)?;
 
         let init_input = InitInput {
-            network: eth2_utility::consensus::Network::from_str(&self.config.init.network)
-                .map_err(|err| color_eyre::eyre::eyre!("Incorrect network name: {err:?}"))?,
+            network: self.config.init.ethereum_network()?,
             finalized_execution_header: headers[0].clone(),
             finalized_beacon_header: first_update.finalized_header.clone().into(),
             current_sync_committee: init_update
@@ -159,6 +157,60 @@ impl EthRelayer {
             .map_err(Into::into)
     }
 
+    /// Init the contract from the light client bootstrap of the trusted checkpoint.
+    /// The contract verifies the bootstrap against the checkpoint root and takes the next
+    /// sync committee from the update of the bootstrap period.
+    pub async fn init_eth_client_from_bootstrap(&self) -> Result<()> {
+        let trusted_block_root = self
+            .config
+            .init
+            .trusted_block_root
+            .clone()
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "The trusted block root is required for the initialization from bootstrap, set `init.trusted_block_root` to a recent finalized checkpoint root from a trusted source"
+                )
+            })?;
+
+        let bootstrap = self
+            .beacon_client
+            .fetch_bootstrap(&trusted_block_root)
+            .await?;
+        let period = BeaconClient::get_period_for_slot(bootstrap.header.beacon.slot);
+        let update = self.beacon_client.fetch_period_update(period).await?;
+
+        let block_number = bootstrap.header.execution.block_number;
+        let finalized_execution_header = self
+            .execution_client
+            .fetch_block_header(block_number)
+            .await?
+            .ok_or_else(|| color_eyre::eyre::eyre!("Execution block {} not found", block_number))?;
+
+        info!(
+            "Initializing from bootstrap at slot {} (period {}), execution block {}",
+            bootstrap.header.beacon.slot, period, block_number
+        );
+
+        let init_input = BootstrapInitInput {
+            network: self.config.init.ethereum_network()?,
+            trusted_block_root: serde_json::from_value(serde_json::Value::String(
+                trusted_block_root,
+            ))
+            .map_err(|err| color_eyre::eyre::eyre!("Invalid trusted block root: {err}"))?,
+            bootstrap,
+            update,
+            finalized_execution_header,
+            validate_updates: true,
+            hashes_gc_threshold: self.config.init.hashes_gc_threshold,
+            trusted_signer: None,
+        };
+
+        self.near_client
+            .init_contract_from_bootstrap(init_input)
+            .await
+            .map_err(Into::into)
+    }
+
     async fn run_iteration(&self) -> RelayResult {
         // Early return pattern - convert all errors to RelayResult::Error
         let mode = match self.get_mode_if_synced().await {
@@ -207,8 +259,29 @@ impl EthRelayer {
             return Ok(RelayResult::Skipped);
         }
 
-        let update = self.fetch_update_for_slots(near_slot, eth_slot).await?;
-        self.near_client.submit_light_client_update(update).await?;
+        let near_period = BeaconClient::get_period_for_slot(near_slot);
+        let eth_period = BeaconClient::get_period_for_slot(eth_slot);
+        if eth_period > near_period + 1 {
+            // Catch up several periods in one transaction, the committees are rotated period by period
+            let count =
+                (eth_period - near_period).min(self.config.relayer.max_updates_per_batch as u64);
+            info!(
+                "NEAR client is {} periods behind, submitting updates for periods {}..={}",
+                eth_period - near_period,
+                near_period + 1,
+                near_period + count
+            );
+            let updates = self
+                .beacon_client
+                .fetch_period_updates(near_period + 1, count)
+                .await?;
+            self.near_client
+                .submit_light_client_updates(updates)
+                .await?;
+        } else {
+            let update = self.fetch_update_for_slots(near_slot, eth_slot).await?;
+            self.near_client.submit_light_client_update(update).await?;
+        }
 
         Ok(RelayResult::Submitted)
     }
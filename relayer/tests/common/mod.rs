@@ -208,6 +208,14 @@ pub fn load_test_light_client_updates() -> Result<(LightClientUpdate, LightClien
     Ok((init_update, first_update))
 }
 
+/// Load test light client update of the given period
+pub fn load_test_light_client_update(period: u64) -> Result<LightClientUpdate> {
+    let update: LightClientUpdate = serde_json::from_reader(std::fs::File::open(format!(
+        "./tests/data/light_client_update_period_{period}.json"
+    ))?)?;
+    Ok(update)
+}
+
 // near_workspaces::compile_project
 pub async fn compile_eth2_client_testnet() -> crate::Result<Vec<u8>> {
     let project_path = "../contracts/near/eth2-client";
@@ -3,7 +3,9 @@ use eth_types::H256;
 use eth2_utility::types::ClientMode;
 
 mod common;
-use common::{TestFixture, load_test_headers, load_test_light_client_updates};
+use common::{
+    TestFixture, load_test_headers, load_test_light_client_update, load_test_light_client_updates,
+};
 
 #[tokio::test]
 async fn test_full_lifecycle_smoke_test() -> Result<()> {
@@ -108,6 +110,36 @@ async fn test_light_client_update_submission() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_light_client_updates_batch_submission() -> Result<()> {
+    let fixture = TestFixture::new().await?;
+    fixture.init_with_sepolia_no_validation().await?;
+
+    // The client is initialized at period 925, catch up two periods at once
+    let updates = vec![
+        load_test_light_client_update(926)?,
+        load_test_light_client_update(927)?,
+    ];
+    let last_finalized_slot = updates[1].finalized_header.beacon.slot;
+
+    fixture
+        .near_client
+        .submit_light_client_updates(updates)
+        .await?;
+
+    let finalized_slot = fixture
+        .near_client
+        .get_finalized_beacon_block_slot()
+        .await?;
+    assert_eq!(finalized_slot, last_finalized_slot);
+
+    let mode = fixture.near_client.get_client_mode().await?;
+    assert_eq!(mode, ClientMode::SubmitHeader);
+
+    println!("✅ Light client updates batch submission test passed");
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_execution_headers_submission() -> Result<()> {
     let fixture = TestFixture::new().await?;